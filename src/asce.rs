@@ -0,0 +1,89 @@
+use core::f64::consts::PI;
+
+/// Stefan-Boltzmann constant in MJ/K4/m2/day as used by the ASCE Standardized equation.
+pub(crate) const SIGMA: f64 = 4.901e-9;
+
+/// Atmospheric pressure in kPa at an elevation in meters (Eq. 3).
+pub(crate) fn pressure(z: f64) -> f64 {
+    101.3 * libm::pow((293.0 - 0.0065 * z) / 293.0, 5.26)
+}
+
+/// Psychrometric constant in kPa/C from the atmospheric pressure in kPa (Eq. 4).
+pub(crate) fn psy_constant(pressure: f64) -> f64 {
+    0.000665 * pressure
+}
+
+/// Saturation vapor pressure in kPa at a temperature in Celsius (Eq. 7).
+pub(crate) fn eo(temp: f64) -> f64 {
+    0.6108 * libm::exp((17.27 * temp) / (temp + 237.3))
+}
+
+/// Daily saturation vapor pressure in kPa, the mean of eo at Tmax and Tmin (Eq. 6).
+pub(crate) fn es(tmax: f64, tmin: f64) -> f64 {
+    (eo(tmax) + eo(tmin)) / 2.0
+}
+
+/// Slope of the saturation vapor pressure curve in kPa/C at the mean temperature (Eq. 5).
+pub(crate) fn es_slope(tmean: f64) -> f64 {
+    2503.0 * libm::exp((17.27 * tmean) / (tmean + 237.3)) / libm::pow(tmean + 237.3, 2.0)
+}
+
+/// Inverse relative distance factor of the Earth to the Sun (Eq. 23).
+pub(crate) fn inverse_rel_dist_factor(doy: u32, days_in_year: f64) -> f64 {
+    1.0 + 0.033 * libm::cos(2.0 * PI / days_in_year * doy as f64)
+}
+
+/// Solar declination in radians (Eq. 24).
+pub(crate) fn solar_declin(doy: u32, days_in_year: f64) -> f64 {
+    0.409 * libm::sin(2.0 * PI / days_in_year * doy as f64 - 1.39)
+}
+
+/// Sunset hour angle in radians (Eq. 27), 0 during polar night and π during polar day.
+pub(crate) fn sunset_hour_angle(latitude_rad: f64, declination: f64) -> f64 {
+    // above the polar circles the argument leaves [-1, 1] and acos would return NaN
+    libm::acos((-libm::tan(latitude_rad) * libm::tan(declination)).clamp(-1.0, 1.0))
+}
+
+/// Extraterrestrial radiation in MJ/m2/day for a 24 hour period (Eq. 21).
+pub(crate) fn ra(latitude_rad: f64, doy: u32, days_in_year: f64) -> f64 {
+    let dr = inverse_rel_dist_factor(doy, days_in_year);
+    let declination = solar_declin(doy, days_in_year);
+    let omega = sunset_hour_angle(latitude_rad, declination);
+
+    24.0 / PI
+        * 4.92
+        * dr
+        * (omega * libm::sin(latitude_rad) * libm::sin(declination)
+            + libm::cos(latitude_rad) * libm::cos(declination) * libm::sin(omega))
+}
+
+/// Clear-sky solar radiation in MJ/m2/day from Ra and the elevation in meters (Eq. 19).
+pub(crate) fn rso(ra: f64, z: f64) -> f64 {
+    (0.75 + 2e-5 * z) * ra
+}
+
+/// Cloudiness function (Eq. 18), with Rs/Rso limited to 0.3 to 1. An Rs or Rso of 0 is treated
+/// as fully overcast rather than dividing by zero.
+pub(crate) fn fcd(rso: f64, rs: f64) -> f64 {
+    if rs <= 0.0 || rso <= 0.0 {
+        return 0.3 * 1.35 - 0.35;
+    }
+
+    1.35 * (rs / rso).clamp(0.3, 1.0) - 0.35
+}
+
+/// Net long-wave radiation in MJ/m2/day (Eq. 17) from the cloudiness function, the net
+/// emissivity, and the Stefan-Boltzmann constant in MJ/K4/m2/day.
+pub(crate) fn rnl(fcd: f64, net_emissivity: f64, tmax: f64, tmin: f64, sigma: f64) -> f64 {
+    sigma * fcd * net_emissivity * (libm::pow(tmax + 273.16, 4.0) + libm::pow(tmin + 273.16, 4.0))
+        / 2.0
+}
+
+/// Wind speed at 2 m from a speed measured at `wz` meters (Eq. 33).
+pub(crate) fn u2(ws: f64, wz: f64) -> f64 {
+    if wz == 2.0 {
+        return ws;
+    }
+
+    ws * 4.87 / libm::log(67.8 * wz - 5.42)
+}
@@ -0,0 +1,187 @@
+use crate::et::check_rs;
+use crate::EaInput;
+use climate::output::Output;
+use std::f64::consts::FRAC_PI_2;
+
+/// Overall data tier of a record, from fully instrumented down to unusable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletenessTier {
+    Full,            // temperature, humidity, radiation, wind, and site are all present
+    Partial,         // some measured inputs are missing, see `input_completeness` for fallbacks
+    TemperatureOnly, // humidity and radiation come from temperature, wind needs the robust default
+    Insufficient,    // temperature, site, humidity, or Rs is unusable, ET cannot be calculated
+}
+
+/// Summary of which input groups a record provides before the reference ET is calculated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Completeness {
+    pub temperature: bool, // tmax and tmin are finite and tmax >= tmin
+    pub humidity: bool,    // ea, dewpoint, or relative humidity is measured and gives a valid ea
+    pub radiation: bool,   // solar radiation is measured and valid
+    pub wind: bool,        // wind speed is measured
+    pub site: bool,        // elevation and latitude are valid
+    pub tier: CompletenessTier,
+}
+
+/// Reports which inputs a record provides, using the same fields the calculation checks.
+///
+/// Missing humidity falls back to the Tmin dewpoint estimate and missing radiation falls back to
+/// Hargreaves-Samani, so those gaps lower the tier without making the record unusable. Missing
+/// wind has no estimate: `calculate_ref_et` returns `MissingField("ws")` for it, and only
+/// `calculate_ref_et_robust` substitutes the FAO-56 default of 2 m/s.
+///
+/// Humidity and Rs are checked the way the calculation checks them, so a measured value the
+/// calculation would reject, such as a negative Rs or a relative humidity above 100, makes the
+/// record `Insufficient` rather than counting as measured.
+///
+/// # Arguments
+///
+/// * `input` - The output record to inspect.
+///
+/// # Returns
+///
+/// * A `Completeness` with a flag for each input group and the overall tier.
+pub fn input_completeness(input: &Output) -> Completeness {
+    let temperature = input.get_tmax().is_finite()
+        && input.get_tmin().is_finite()
+        && input.get_tmax() >= input.get_tmin();
+    let measured_humidity = input.get_ea().is_some()
+        || input.get_dewpoint().is_some()
+        || input.get_rhmax().is_some()
+        || input.get_rhmin().is_some();
+    // the same ea resolution as the calculation, which only falls back to Tmin without humidity
+    let ea_valid = EaInput::new_from_output(input)
+        .and_then(|ea_input| ea_input.ea())
+        .is_ok();
+    let rs_valid = check_rs(input.get_rs()).is_ok();
+    let humidity = measured_humidity && ea_valid;
+    let radiation = input.get_rs().is_some() && rs_valid;
+    let wind = input.get_ws().is_some();
+    let site = input.get_z().is_finite()
+        && input.get_latitude().is_finite()
+        && input.get_latitude().abs() <= FRAC_PI_2;
+
+    let tier = if !temperature || !site || !ea_valid || !rs_valid {
+        CompletenessTier::Insufficient
+    } else if humidity && radiation && wind {
+        CompletenessTier::Full
+    } else if !humidity && !radiation && !wind {
+        CompletenessTier::TemperatureOnly
+    } else {
+        CompletenessTier::Partial
+    };
+
+    Completeness {
+        temperature,
+        humidity,
+        radiation,
+        wind,
+        site,
+        tier,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_input_completeness_temperature_only() {
+        // Given
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(2.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When
+        let completeness = input_completeness(&output);
+
+        assert!(completeness.temperature);
+        assert!(completeness.site);
+        assert!(!completeness.humidity);
+        assert!(!completeness.radiation);
+        assert!(!completeness.wind);
+        assert_eq!(completeness.tier, CompletenessTier::TemperatureOnly);
+    }
+
+    #[test]
+    fn test_input_completeness_full() {
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        assert_eq!(input_completeness(&output).tier, CompletenessTier::Full);
+    }
+
+    #[test]
+    fn test_input_completeness_negative_rs() {
+        // Given, a logger reporting a negative Rs at night
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(-0.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When
+        let completeness = input_completeness(&output);
+
+        assert!(!completeness.radiation);
+        assert_eq!(completeness.tier, CompletenessTier::Insufficient);
+        assert!(crate::calculate_ref_et(&output).is_err());
+    }
+
+    #[test]
+    fn test_input_completeness_invalid_humidity() {
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            Some(150.0),
+            Some(30.0),
+            None,
+            None,
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        let completeness = input_completeness(&output);
+
+        assert!(!completeness.humidity);
+        assert_eq!(completeness.tier, CompletenessTier::Insufficient);
+        assert!(crate::calculate_ref_et(&output).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use crate::asce;
+
+/// Short reference numerator constant (Cn) for daily time steps in the ASCE Standardized equation.
+const ASCE_CN_SHORT: f64 = 900.0;
+/// Short reference denominator constant (Cd) for daily time steps.
+const ASCE_CD_SHORT: f64 = 0.34;
+/// Tall reference numerator constant (Cn) for daily time steps.
+const ASCE_CN_TALL: f64 = 1600.0;
+/// Tall reference denominator constant (Cd) for daily time steps.
+const ASCE_CD_TALL: f64 = 0.38;
+/// Albedo of the grass and alfalfa reference surfaces.
+const ASCE_ALBEDO: f64 = 0.23;
+/// Hargreaves-Samani Krs coefficients the ASCE manual gives for interior and coastal sites.
+const ASCE_KRS: [f64; 2] = [0.16, 0.19];
+/// Range of the Tmin dewpoint offset Ko given in ASCE Appendix E.
+const ASCE_KO_RANGE: (f64, f64) = (0.0, 4.0);
+
+/// Configuration for the reference ET calculation.
+///
+/// The `Default` implementation matches the ASCE Standardized Reference ET equation, so only
+/// callers calibrating for a specific site or study need to change anything.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefEtConfig {
+    /// Offset in degrees Celsius subtracted from Tmin to estimate the dewpoint when no humidity
+    /// data is available (ASCE Appendix E, Eq. E.1: Tdew = Tmin - Ko).
+    ///
+    /// Appendix E gives Ko as about 0 °C for humid and sub-humid sites and 2 to 4 °C for arid and
+    /// semi-arid sites. The default of 3 °C is the arid value this crate has always applied, which
+    /// suits the irrigated semi-arid stations it was written for. Humid sites should use 0.
+    pub tmin_dewpoint_offset: f64,
+    /// Albedo of the reference surface used for net short-wave radiation (Eq. 16).
+    pub albedo: f64,
+    /// Stefan-Boltzmann constant in MJ/K4/m2/day used for net long-wave radiation (Eq. 17).
+    pub sigma: f64,
+    /// Numerator constant for the short reference crop.
+    pub cn_short: f64,
+    /// Denominator constant for the short reference crop.
+    pub cd_short: f64,
+    /// Numerator constant for the tall reference crop.
+    pub cn_tall: f64,
+    /// Denominator constant for the tall reference crop.
+    pub cd_tall: f64,
+    /// Krs coefficient of the Hargreaves-Samani Rs estimate used when Rs is missing.
+    pub hargreaves_krs: f64,
+    /// Angstrom a_s coefficient for Rs from sunshine hours, the FAO-56 value without calibration.
+    pub angstrom_a: f64,
+    /// Angstrom b_s coefficient for Rs from sunshine hours, the FAO-56 value without calibration.
+    pub angstrom_b: f64,
+    /// Limits the Hargreaves-Samani Rs estimate to clear sky radiation (Rso).
+    pub clamp_hargreaves_rs: bool,
+    /// Limits measured Rs to clear sky radiation (Rso). ASCE Standardized uses measured Rs as
+    /// supplied and only limits Rs/Rso in the cloudiness function, so this is off by default.
+    pub clamp_measured_rs: bool,
+    /// Adjusts wind to 2 m with the logarithmic profile over the 0.5 m alfalfa surface for the tall
+    /// reference only. ASCE Standardized uses the grass profile of Eq. 33 for both references, so
+    /// this is off by default.
+    pub alfalfa_wind_profile: bool,
+    /// Units of the temperatures carried by an `Output`, "C", "F", or "K". Temperatures and dewpoints
+    /// are converted to Celsius before any ea method or ET term uses them.
+    pub temperature_units: String,
+}
+
+impl Default for RefEtConfig {
+    fn default() -> Self {
+        RefEtConfig {
+            tmin_dewpoint_offset: 3.0,
+            albedo: ASCE_ALBEDO,
+            sigma: asce::SIGMA,
+            cn_short: ASCE_CN_SHORT,
+            cd_short: ASCE_CD_SHORT,
+            cn_tall: ASCE_CN_TALL,
+            cd_tall: ASCE_CD_TALL,
+            hargreaves_krs: ASCE_KRS[0],
+            angstrom_a: 0.25,
+            angstrom_b: 0.50,
+            clamp_hargreaves_rs: true,
+            clamp_measured_rs: false,
+            alfalfa_wind_profile: false,
+            temperature_units: "C".to_string(),
+        }
+    }
+}
+
+/// Numerator and denominator constants of the daily ASCE Standardized equation for both reference
+/// crops, for calibrating the equation to a new region.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EtCoefficients {
+    /// Numerator constant for the short reference crop.
+    pub cn_short: f64,
+    /// Denominator constant for the short reference crop.
+    pub cd_short: f64,
+    /// Numerator constant for the tall reference crop.
+    pub cn_tall: f64,
+    /// Denominator constant for the tall reference crop.
+    pub cd_tall: f64,
+    /// Adjusts wind to 2 m over the alfalfa surface for the tall reference only, off by default so
+    /// the result matches ASCE Standardized. See `RefEtConfig::alfalfa_wind_profile`.
+    pub alfalfa_wind_profile: bool,
+}
+
+impl Default for EtCoefficients {
+    fn default() -> Self {
+        EtCoefficients {
+            cn_short: ASCE_CN_SHORT,
+            cd_short: ASCE_CD_SHORT,
+            cn_tall: ASCE_CN_TALL,
+            cd_tall: ASCE_CD_TALL,
+            alfalfa_wind_profile: false,
+        }
+    }
+}
+
+/// Checks a configuration against the ASCE Standardized Reference ET specification.
+///
+/// This only inspects the configuration, it does not run the calculation.
+///
+/// # Arguments
+///
+/// * `config` - The configuration to check.
+///
+/// # Returns
+///
+/// * `Ok(())` when the configuration is ASCE compliant, otherwise every deviation found.
+pub fn verify_asce_compliance(config: &RefEtConfig) -> Result<(), Vec<String>> {
+    let mut deviations = Vec::new();
+
+    let constants = [
+        ("albedo", config.albedo, ASCE_ALBEDO),
+        ("sigma", config.sigma, asce::SIGMA),
+        ("cn_short", config.cn_short, ASCE_CN_SHORT),
+        ("cd_short", config.cd_short, ASCE_CD_SHORT),
+        ("cn_tall", config.cn_tall, ASCE_CN_TALL),
+        ("cd_tall", config.cd_tall, ASCE_CD_TALL),
+    ];
+    for (name, value, expected) in constants {
+        if !approx_eq(value, expected) {
+            deviations.push(format!(
+                "{} is {}, ASCE Standardized uses {}",
+                name, value, expected
+            ));
+        }
+    }
+
+    if !ASCE_KRS
+        .iter()
+        .any(|&krs| approx_eq(config.hargreaves_krs, krs))
+    {
+        deviations.push(format!(
+            "hargreaves_krs is {}, ASCE Standardized uses 0.16 for interior or 0.19 for coastal sites",
+            config.hargreaves_krs
+        ));
+    }
+
+    if !config.clamp_hargreaves_rs {
+        deviations.push(
+            "clamp_hargreaves_rs is false, ASCE Standardized limits estimated Rs to Rso"
+                .to_string(),
+        );
+    }
+
+    if config.alfalfa_wind_profile {
+        deviations.push(
+            "alfalfa_wind_profile is true, ASCE Standardized uses Eq. 33 for both references"
+                .to_string(),
+        );
+    }
+
+    let (ko_min, ko_max) = ASCE_KO_RANGE;
+    if !(ko_min..=ko_max).contains(&config.tmin_dewpoint_offset) {
+        deviations.push(format!(
+            "tmin_dewpoint_offset is {}, ASCE Appendix E uses {} to {}",
+            config.tmin_dewpoint_offset, ko_min, ko_max
+        ));
+    }
+
+    if deviations.is_empty() {
+        Ok(())
+    } else {
+        Err(deviations)
+    }
+}
+
+fn approx_eq(value: f64, expected: f64) -> bool {
+    (value - expected).abs() <= expected.abs() * 1e-9
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_asce_compliant() {
+        assert!(verify_asce_compliance(&RefEtConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn test_tweaked_albedo_reports_deviation() {
+        // Given
+        let config = RefEtConfig {
+            albedo: 0.25,
+            ..RefEtConfig::default()
+        };
+
+        // When
+        let result = verify_asce_compliance(&config);
+
+        let deviations = result.unwrap_err();
+        assert_eq!(deviations.len(), 1);
+        assert!(deviations[0].contains("albedo"));
+    }
+
+    #[test]
+    fn test_multiple_deviations_reported() {
+        let config = RefEtConfig {
+            sigma: 5.0e-9,
+            cd_tall: 0.34,
+            clamp_hargreaves_rs: false,
+            ..RefEtConfig::default()
+        };
+
+        assert_eq!(verify_asce_compliance(&config).unwrap_err().len(), 3);
+    }
+}
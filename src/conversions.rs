@@ -1,59 +1,273 @@
+use crate::et::eo;
+use crate::RefEtError;
 use chrono::{Datelike, NaiveDate};
+use std::f64::consts::PI;
 
-// pub fn c_to_f(value: f64) -> f64 {
-//     // conversion of Celsius to Fahrenheit
-//     value * 9.0 / 5.0 + 32.0
-// }
-
-// pub fn f_to_c(value: f64) -> f64 {
-//     // conversion of Fahrenheit to celsius
-//     (value - 32.0) * 5.0 / 9.0
-// }
-
-// pub fn pa_to_kpa(value: f64) -> f64 {
-//     // conversion of pascals to kilopascals
-//     value / 1000.0
-// }
-
-// pub fn lang_to_mj(value: f64) -> f64 {
-//     // conversion of megajoules to watts
-//     value * 0.04184
-// }
-
-// pub fn mj_to_lang(value: f64) -> f64 {
-//     // conversion of megajoules to watts
-//     value / 0.04184
-// }
-
-// pub fn watts_to_mj(value: f64) -> f64 {
-//     // conversion of watts to megajoules
-//     value * 0.0864
-// }
-
-// pub fn mph_to_mps(value: f64) -> f64 {
-//     // conversion of miles per hour to meters per second
-//     value * 0.44704
-// }
-
-// pub fn mps_to_mph(value: f64) -> f64 {
-//     // conversion of meters per second to miles per hour
-//     value / 0.44704
-// }
-
-// pub fn feet_to_meters(value: f64) -> f64 {
-//     // conversion of feet to meters
-//     value * 0.3048
-// }
-
-// pub fn degrees_to_radians(degrees: f64) -> f64 {
-//     // conversion of degrees to radians
-//     degrees * PI / 180.0
-// }
-
-// fn radians_to_degrees(radians: f64) -> f64 {
-//     // conversion of radians to degrees
-//     radians * 180.0 / PI
-// }
+/// Ratio of the molecular weight of water vapor to dry air.
+const EPSILON: f64 = 0.622;
+
+/// Typical ratio of grass reference ETo to alfalfa reference ETr. The ratio is lower in windy,
+/// arid climates and higher in calm, humid ones, so local ratios are better where available.
+pub const ETO_ETR_RATIO: f64 = 0.8;
+
+pub fn c_to_f(value: f64) -> f64 {
+    // conversion of Celsius to Fahrenheit
+    value * 9.0 / 5.0 + 32.0
+}
+
+pub fn f_to_c(value: f64) -> f64 {
+    // conversion of Fahrenheit to celsius
+    (value - 32.0) * 5.0 / 9.0
+}
+
+pub fn k_to_c(value: f64) -> f64 {
+    // conversion of Kelvin to Celsius
+    value - 273.15
+}
+
+pub fn pa_to_kpa(value: f64) -> f64 {
+    // conversion of pascals to kilopascals
+    value / 1000.0
+}
+
+pub fn lang_to_mj(value: f64) -> f64 {
+    // conversion of langleys to megajoules per square meter
+    value * 0.04184
+}
+
+pub fn mj_to_lang(value: f64) -> f64 {
+    // conversion of megajoules per square meter to langleys
+    value / 0.04184
+}
+
+pub fn watts_to_mj(value: f64) -> f64 {
+    // conversion of a daily mean in watts per square meter to megajoules per square meter per day
+    value * 0.0864
+}
+
+pub fn mph_to_mps(value: f64) -> f64 {
+    // conversion of miles per hour to meters per second
+    value * 0.44704
+}
+
+pub fn mps_to_mph(value: f64) -> f64 {
+    // conversion of meters per second to miles per hour
+    value / 0.44704
+}
+
+pub fn feet_to_meters(value: f64) -> f64 {
+    // conversion of feet to meters
+    value * 0.3048
+}
+
+/// Converts a wind run, the distance the wind travelled over a period, to the mean wind speed.
+///
+/// # Arguments
+///
+/// * `run` - Wind run over the period.
+/// * `units` - Units of the distance, "km" or "mi", optionally per day as "km/day" or "mi/day".
+/// * `period_hours` - Length of the period in hours, 24 for a daily wind run.
+///
+/// # Returns
+///
+/// * The mean wind speed in m/s, or `InvalidUnits` when the distance units aren't recognized.
+pub fn wind_run_to_speed(run: f64, units: &str, period_hours: f64) -> Result<f64, RefEtError> {
+    let meters = match units.trim().to_lowercase().as_str() {
+        "km" | "km/day" => run * 1000.0,
+        "mi" | "mi/day" | "miles" | "miles/day" => run * 1609.344,
+        _ => return Err(RefEtError::InvalidUnits(units.to_string())),
+    };
+
+    Ok(meters / (period_hours * 3600.0))
+}
+
+pub fn degrees_to_radians(degrees: f64) -> f64 {
+    // conversion of degrees to radians
+    degrees * PI / 180.0
+}
+
+pub fn radians_to_degrees(radians: f64) -> f64 {
+    // conversion of radians to degrees
+    radians * 180.0 / PI
+}
+
+/// Converts alfalfa reference ETr to grass reference ETo with an ETo/ETr ratio.
+///
+/// # Arguments
+/// * `etr` - Alfalfa reference ET.
+/// * `ratio` - The ETo/ETr ratio, `ETO_ETR_RATIO` when no local ratio is known.
+///
+/// # Returns
+/// * Grass reference ET in the units of `etr`.
+pub fn etr_to_eto(etr: f64, ratio: f64) -> f64 {
+    etr * ratio
+}
+
+/// Converts grass reference ETo to alfalfa reference ETr with an ETo/ETr ratio.
+///
+/// # Arguments
+/// * `eto` - Grass reference ET.
+/// * `ratio` - The ETo/ETr ratio, `ETO_ETR_RATIO` when no local ratio is known.
+///
+/// # Returns
+/// * Alfalfa reference ET in the units of `eto`.
+pub fn eto_to_etr(eto: f64, ratio: f64) -> f64 {
+    eto / ratio
+}
+
+/// Picks the ETo/ETr ratio for a month from a table of twelve monthly ratios, January first,
+/// such as one fitted to a station's own ASCE short and tall reference series.
+///
+/// # Arguments
+/// * `month` - The month, 1 for January to 12 for December.
+/// * `ratios` - The monthly ratios.
+///
+/// # Returns
+/// * The ratio for the month, or `ETO_ETR_RATIO` when `month` is not 1 to 12.
+pub fn monthly_ratio(month: u32, ratios: &[f64; 12]) -> f64 {
+    ratios
+        .get((month as usize).wrapping_sub(1))
+        .copied()
+        .unwrap_or(ETO_ETR_RATIO)
+}
+
+/// Parses a latitude in degrees, minutes, and seconds such as "40°24'36\"N" or "40 24 36 N".
+///
+/// Minutes and seconds are optional. Components may be separated by spaces, ASCII (' ") or
+/// unicode (° ′ ″) marks, and the hemisphere is taken from an N or S suffix or a leading minus.
+///
+/// # Arguments
+/// * `s` - The latitude text.
+///
+/// # Returns
+/// * The latitude in signed decimal degrees, negative in the southern hemisphere, or an error
+///   when the text can't be parsed or is beyond 90 degrees.
+pub fn parse_latitude_dms(s: &str) -> Result<f64, RefEtError> {
+    let invalid = || RefEtError::DomainError("latitude must be degrees, minutes, and seconds");
+    let trimmed = s.trim();
+
+    let (body, hemisphere) = match trimmed.chars().last() {
+        Some('N' | 'n') => (&trimmed[..trimmed.len() - 1], 1.0),
+        Some('S' | 's') => (&trimmed[..trimmed.len() - 1], -1.0),
+        _ => (trimmed, 1.0),
+    };
+    let (body, sign) = match body.trim().strip_prefix('-') {
+        Some(rest) => (rest, -hemisphere),
+        None => (body, hemisphere),
+    };
+
+    let parts = body
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<f64>, RefEtError>>()?;
+    if parts.is_empty() || parts.len() > 3 {
+        return Err(invalid());
+    }
+
+    let minutes = parts.get(1).copied().unwrap_or(0.0);
+    let seconds = parts.get(2).copied().unwrap_or(0.0);
+    if minutes >= 60.0 || seconds >= 60.0 {
+        return Err(invalid());
+    }
+
+    let degrees = parts[0] + minutes / 60.0 + seconds / 3600.0;
+    if degrees > 90.0 {
+        return Err(RefEtError::OutOfRange {
+            field: "latitude",
+            value: sign * degrees,
+        });
+    }
+
+    Ok(sign * degrees)
+}
+
+/// Converts relative humidity to actual vapor pressure (FAO-56 Eq. 10).
+///
+/// # Arguments
+/// * `rh_percent` - Relative humidity in %.
+/// * `temp_c` - Air temperature in Celsius.
+///
+/// # Returns
+/// * Actual vapor pressure in kPa.
+pub fn rh_to_ea(rh_percent: f64, temp_c: f64) -> f64 {
+    eo(temp_c) * rh_percent / 100.0
+}
+
+/// Converts actual vapor pressure to relative humidity (FAO-56 Eq. 10).
+///
+/// # Arguments
+/// * `ea_kpa` - Actual vapor pressure in kPa.
+/// * `temp_c` - Air temperature in Celsius.
+///
+/// # Returns
+/// * Relative humidity in %.
+pub fn ea_to_rh(ea_kpa: f64, temp_c: f64) -> f64 {
+    100.0 * ea_kpa / eo(temp_c)
+}
+
+/// Calculates the dewpoint from relative humidity by inverting the saturation vapor pressure
+/// curve (Eq. 7) at the actual vapor pressure.
+///
+/// # Arguments
+/// * `rh_percent` - Relative humidity in %, above 0.
+/// * `temp_c` - Air temperature in Celsius.
+///
+/// # Returns
+/// * Dewpoint temperature in Celsius.
+pub fn dewpoint_from_rh(rh_percent: f64, temp_c: f64) -> f64 {
+    let x = (rh_to_ea(rh_percent, temp_c) / 0.6108).ln();
+    237.3 * x / (17.27 - x)
+}
+
+/// Calculates relative humidity from the dewpoint (Eq. 7 and 8).
+///
+/// # Arguments
+/// * `tdew_c` - Dewpoint temperature in Celsius.
+/// * `temp_c` - Air temperature in Celsius.
+///
+/// # Returns
+/// * Relative humidity in %.
+pub fn rh_from_dewpoint(tdew_c: f64, temp_c: f64) -> f64 {
+    ea_to_rh(eo(tdew_c), temp_c)
+}
+
+/// Converts specific humidity to actual vapor pressure.
+///
+/// # Arguments
+/// * `q` - Specific humidity in kg/kg.
+/// * `pressure_kpa` - Atmospheric pressure in kPa.
+///
+/// # Returns
+/// * Actual vapor pressure in kPa.
+pub fn specific_humidity_to_ea(q: f64, pressure_kpa: f64) -> f64 {
+    q * pressure_kpa / (EPSILON + (1.0 - EPSILON) * q)
+}
+
+/// Converts actual vapor pressure to specific humidity.
+///
+/// # Arguments
+/// * `ea_kpa` - Actual vapor pressure in kPa.
+/// * `pressure_kpa` - Atmospheric pressure in kPa.
+///
+/// # Returns
+/// * Specific humidity in kg/kg.
+pub fn ea_to_specific_humidity(ea_kpa: f64, pressure_kpa: f64) -> f64 {
+    EPSILON * ea_kpa / (pressure_kpa - (1.0 - EPSILON) * ea_kpa)
+}
+
+/// Converts a given date to the day of the year.
+///
+/// # Arguments
+/// * `date` - The date.
+///
+/// # Returns
+/// * Ok(u32): the day of the year as a u32, this never fails.
+///
+pub fn day_of_year(date: &NaiveDate) -> Result<u32, String> {
+    // Get the day of the year
+    Ok(date.ordinal())
+}
 
 /// Converts a given date (in the format yyyy-mm-dd) to the day of the year.
 ///
@@ -63,10 +277,12 @@ use chrono::{Datelike, NaiveDate};
 /// # Returns
 /// * A Result that is either:
 ///   - Ok(u32): the day of the year as a u32 if the input date is valid.
-///   - Err(String): an error string indicating what went wrong (e.g., invalid date format).
+///   - Err(RefEtError): a domain error when the date is malformed or doesn't exist.
 ///
-pub fn day_of_year(date: &NaiveDate) -> Result<u32, String> {
-    // Get the day of the year
+pub fn day_of_year_from_str(date_str: &str) -> Result<u32, RefEtError> {
+    let date = NaiveDate::parse_from_str(date_str.trim(), "%Y-%m-%d").map_err(|_| {
+        RefEtError::DomainError("date must be a valid date in the format YYYY-MM-DD")
+    })?;
     Ok(date.ordinal())
 }
 
@@ -91,84 +307,184 @@ mod tests {
         assert_eq!(day_of_year, 60);
     }
 
-    // #[test]
-    // fn test_c_to_f() {
-    //     assert_eq!(c_to_f(0.0), 32.0);
-    //     assert_eq!(c_to_f(100.0), 212.0);
-    //     assert_eq!(c_to_f(-40.0), -40.0);
-    // }
-
-    // #[test]
-    // fn test_f_to_c() {
-    //     assert_eq!(f_to_c(32.0), 0.0);
-    //     assert_eq!(f_to_c(212.0), 100.0);
-    //     assert_eq!(f_to_c(-40.0), -40.0);
-    // }
-
-    // #[test]
-    // fn test_pa_to_kpa() {
-    //     assert_eq!(pa_to_kpa(1000.0), 1.0);
-    //     assert_eq!(pa_to_kpa(250000.0), 250.0);
-    //     assert_eq!(pa_to_kpa(0.0), 0.0);
-    // }
-
-    // #[test]
-    // fn test_lang_to_mj() {
-    //     assert_eq!(lang_to_mj(1000.0), 41.84);
-    //     assert_eq!(lang_to_mj(250000.0), 10460.0);
-    //     assert_eq!(lang_to_mj(0.0), 0.0);
-    // }
-
-    // #[test]
-    // fn test_mj_to_lang() {
-    //     let value = ((mj_to_lang(1000.0) * 100.0).round()) / 100.0;
-    //     assert_eq!(value, 23900.57);
-
-    //     let value = ((mj_to_lang(25000.0) * 100.0).round()) / 100.0;
-    //     assert_eq!(value, 597514.34);
-    //     assert_eq!(mj_to_lang(0.0), 0.0);
-    // }
-
-    // #[test]
-    // fn test_mph_to_mps() {
-    //     assert_eq!(mph_to_mps(0.0), 0.0);
-    //     assert_eq!(mph_to_mps(25.0), 11.176);
-    //     assert_eq!(mph_to_mps(75.0), 33.528);
-    // }
-
-    // #[test]
-    // fn test_mps_to_mph() {
-    //     assert_eq!(mps_to_mph(0.0), 0.0);
-    //     assert_eq!(mps_to_mph(11.176), 25.0);
-    //     assert_eq!(mps_to_mph(33.528), 75.0);
-    // }
-
-    // #[test]
-    // fn test_feet_to_meters() {
-    //     assert_eq!(feet_to_meters(0.0), 0.0);
-
-    //     let value = ((feet_to_meters(3.0) * 10000.0).round()) / 10000.0;
-    //     assert_eq!(value, 0.9144);
-
-    //     let value = ((feet_to_meters(12.0) * 10000.0).round()) / 10000.0;
-    //     assert_eq!(value, 3.6576);
-    // }
-
-    // #[test]
-    // fn test_degrees_to_radians() {
-    //     assert_eq!(degrees_to_radians(0.0), 0.0);
-    //     assert_eq!(degrees_to_radians(90.0), PI / 2.0);
-    //     assert_eq!(degrees_to_radians(180.0), PI);
-    //     assert_eq!(degrees_to_radians(270.0), 3.0 * PI / 2.0);
-    //     assert_eq!(degrees_to_radians(360.0), 2.0 * PI);
-    // }
-
-    // #[test]
-    // fn test_radians_to_degrees() {
-    //     assert_eq!(radians_to_degrees(0.0), 0.0);
-    //     assert_eq!(radians_to_degrees(PI / 2.0), 90.0);
-    //     assert_eq!(radians_to_degrees(PI), 180.0);
-    //     assert_eq!(radians_to_degrees(3.0 * PI / 2.0), 270.0);
-    //     assert_eq!(radians_to_degrees(2.0 * PI), 360.0);
-    // }
+    #[test]
+    fn test_day_of_year_from_str() {
+        assert_eq!(day_of_year_from_str("2020-02-29"), Ok(60));
+        assert_eq!(day_of_year_from_str("2021-12-31"), Ok(365));
+        assert!(day_of_year_from_str("2020-13-01").is_err());
+        assert!(day_of_year_from_str("2021-02-29").is_err());
+    }
+
+    #[test]
+    fn test_parse_latitude_dms() {
+        assert!((parse_latitude_dms("40 24 36 N").unwrap() - 40.41).abs() < 0.0001);
+        assert!((parse_latitude_dms("33°52'S").unwrap() - -33.8667).abs() < 0.0001);
+        assert!((parse_latitude_dms("40°24′36″N").unwrap() - 40.41).abs() < 0.0001);
+        assert!((parse_latitude_dms("-12.5").unwrap() - -12.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_parse_latitude_dms_invalid() {
+        assert!(parse_latitude_dms("north").is_err());
+        assert!(parse_latitude_dms("40 75 00 N").is_err());
+        assert!(parse_latitude_dms("95 00 00 S").is_err());
+    }
+
+    #[test]
+    fn test_rh_round_trip() {
+        // Given
+        let ea = 1.27;
+
+        // When
+        let rh = ea_to_rh(ea, 21.65);
+
+        assert!((rh_to_ea(rh, 21.65) - ea).abs() < 1e-6);
+        assert!((rh_to_ea(100.0, 20.0) - 2.3383).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_dewpoint_rh_round_trip() {
+        for (tdew, temp) in [(17.5, 32.0), (9.6, 12.0), (-20.0, -5.0), (21.65, 21.65)] {
+            // When
+            let rh = rh_from_dewpoint(tdew, temp);
+
+            assert!((dewpoint_from_rh(rh, temp) - tdew).abs() < 0.05);
+        }
+        assert!((dewpoint_from_rh(100.0, 20.0) - 20.0).abs() < 0.05);
+        assert!((rh_from_dewpoint(10.0, 20.0) - 52.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_specific_humidity_round_trip() {
+        // Given
+        let ea = 1.27;
+
+        // When
+        let q = ea_to_specific_humidity(ea, 85.17);
+
+        assert!((specific_humidity_to_ea(q, 85.17) - ea).abs() < 1e-6);
+        assert!((q - 0.00933).abs() < 0.00001);
+    }
+
+    #[test]
+    fn test_c_to_f() {
+        assert_eq!(c_to_f(0.0), 32.0);
+        assert_eq!(c_to_f(100.0), 212.0);
+        assert_eq!(c_to_f(-40.0), -40.0);
+    }
+
+    #[test]
+    fn test_f_to_c() {
+        assert_eq!(f_to_c(32.0), 0.0);
+        assert_eq!(f_to_c(212.0), 100.0);
+        assert_eq!(f_to_c(-40.0), -40.0);
+    }
+
+    #[test]
+    fn test_etr_eto_conversion() {
+        // Given, the Greeley tall reference of 7.32 mm/day
+        let etr = 7.32;
+
+        // When
+        let eto = etr_to_eto(etr, ETO_ETR_RATIO);
+
+        assert!((eto - 5.856).abs() < 1e-9);
+        assert!((eto_to_etr(eto, ETO_ETR_RATIO) - etr).abs() < 1e-9);
+        assert!((etr_to_eto(etr, 5.69 / 7.32) - 5.69).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_monthly_ratio() {
+        let mut ratios = [0.8; 12];
+        ratios[6] = 0.78;
+
+        assert_eq!(monthly_ratio(7, &ratios), 0.78);
+        assert_eq!(monthly_ratio(1, &ratios), 0.8);
+        assert_eq!(monthly_ratio(0, &ratios), ETO_ETR_RATIO);
+        assert_eq!(monthly_ratio(13, &ratios), ETO_ETR_RATIO);
+    }
+
+    #[test]
+    fn test_wind_run_to_speed() {
+        let metric = wind_run_to_speed(200.0, "km/day", 24.0).unwrap();
+        let imperial = wind_run_to_speed(124.27, "mi/day", 24.0).unwrap();
+
+        assert!((metric - 2.3148).abs() < 0.0001);
+        assert!((imperial - metric).abs() < 0.001);
+        assert!((wind_run_to_speed(100.0, "km", 12.0).unwrap() - metric).abs() < 1e-12);
+        assert!(wind_run_to_speed(200.0, "knots", 24.0).is_err());
+    }
+
+    #[test]
+    fn test_k_to_c() {
+        assert!((k_to_c(300.0) - 26.85).abs() < 1e-9);
+        assert_eq!(k_to_c(273.15), 0.0);
+    }
+
+    #[test]
+    fn test_pa_to_kpa() {
+        assert_eq!(pa_to_kpa(1000.0), 1.0);
+        assert_eq!(pa_to_kpa(250000.0), 250.0);
+        assert_eq!(pa_to_kpa(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_lang_to_mj() {
+        assert_eq!(lang_to_mj(1000.0), 41.84);
+        assert_eq!(lang_to_mj(250000.0), 10460.0);
+        assert_eq!(lang_to_mj(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_mj_to_lang() {
+        let value = ((mj_to_lang(1000.0) * 100.0).round()) / 100.0;
+        assert_eq!(value, 23900.57);
+
+        let value = ((mj_to_lang(25000.0) * 100.0).round()) / 100.0;
+        assert_eq!(value, 597514.34);
+        assert_eq!(mj_to_lang(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_mph_to_mps() {
+        assert_eq!(mph_to_mps(0.0), 0.0);
+        assert_eq!(mph_to_mps(25.0), 11.176);
+        assert_eq!(mph_to_mps(75.0), 33.528);
+    }
+
+    #[test]
+    fn test_mps_to_mph() {
+        assert_eq!(mps_to_mph(0.0), 0.0);
+        assert_eq!(mps_to_mph(11.176), 25.0);
+        assert_eq!(mps_to_mph(33.528), 75.0);
+    }
+
+    #[test]
+    fn test_feet_to_meters() {
+        assert_eq!(feet_to_meters(0.0), 0.0);
+
+        let value = ((feet_to_meters(3.0) * 10000.0).round()) / 10000.0;
+        assert_eq!(value, 0.9144);
+
+        let value = ((feet_to_meters(12.0) * 10000.0).round()) / 10000.0;
+        assert_eq!(value, 3.6576);
+    }
+
+    #[test]
+    fn test_degrees_to_radians() {
+        assert_eq!(degrees_to_radians(0.0), 0.0);
+        assert_eq!(degrees_to_radians(90.0), PI / 2.0);
+        assert_eq!(degrees_to_radians(180.0), PI);
+        assert_eq!(degrees_to_radians(270.0), 3.0 * PI / 2.0);
+        assert_eq!(degrees_to_radians(360.0), 2.0 * PI);
+    }
+
+    #[test]
+    fn test_radians_to_degrees() {
+        assert_eq!(radians_to_degrees(0.0), 0.0);
+        assert_eq!(radians_to_degrees(PI / 2.0), 90.0);
+        assert_eq!(radians_to_degrees(PI), 180.0);
+        assert_eq!(radians_to_degrees(3.0 * PI / 2.0), 270.0);
+        assert_eq!(radians_to_degrees(2.0 * PI), 360.0);
+    }
 }
@@ -0,0 +1,550 @@
+use crate::RefEtError;
+
+/// Reference ET values at or below this are treated as "no atmospheric demand".
+const MIN_ETO: f64 = 1e-6;
+
+/// Calculates the crop water stress index as the ratio of actual to reference ET (ETa/ETo).
+///
+/// A value of 1.0 means the crop is transpiring at the full reference rate (no stress) and 0.0
+/// means no measurable ET (full stress). Actual ET is typically a remote-sensing product, and
+/// values outside [0, 1] caused by noise in either input are clamped.
+///
+/// # Arguments
+///
+/// * `eta` - Actual evapotranspiration in mm/day.
+/// * `eto` - Reference evapotranspiration in mm/day.
+///
+/// # Returns
+///
+/// The water stress index in the range [0, 1]. When `eto` is effectively zero there is no demand
+/// to be stressed against, so 1.0 is returned.
+pub fn water_stress_index(eta: f64, eto: f64) -> f64 {
+    if eto <= MIN_ETO {
+        return 1.0;
+    }
+
+    (eta / eto).clamp(0.0, 1.0)
+}
+
+/// Calculates the water stress index for paired series of actual and reference ET.
+///
+/// # Arguments
+///
+/// * `eta` - Actual evapotranspiration values in mm/day.
+/// * `eto` - Reference evapotranspiration values in mm/day.
+///
+/// # Returns
+///
+/// A vector of stress indexes, one for each pair. Extra values in the longer slice are ignored.
+pub fn water_stress_index_batch(eta: &[f64], eto: &[f64]) -> Vec<f64> {
+    eta.iter()
+        .zip(eto.iter())
+        .map(|(&a, &o)| water_stress_index(a, o))
+        .collect()
+}
+
+/// Growth stage of a crop for the single crop coefficient approach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CropStage {
+    Initial,     // planting to about 10% ground cover
+    Development, // 10% ground cover to effective full cover
+    Mid,         // effective full cover to the start of maturity
+    Late,        // start of maturity to harvest
+}
+
+/// Crops with built-in FAO-56 crop coefficients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crop {
+    Corn,    // field corn (maize) for grain
+    Alfalfa, // alfalfa hay, averaged over cuttings
+    Wheat,   // spring wheat
+    Soybean, // soybeans for grain
+}
+
+/// Calculates crop evapotranspiration as ETc = Kc * ETo.
+///
+/// # Arguments
+///
+/// * `eto` - Reference evapotranspiration in mm/day.
+/// * `kc` - Crop coefficient.
+///
+/// # Returns
+///
+/// * The crop evapotranspiration in mm/day.
+pub fn apply_crop_coefficient(eto: f64, kc: f64) -> f64 {
+    kc * eto
+}
+
+/// Looks up the single crop coefficient for a crop and growth stage from FAO-56 Table 12.
+///
+/// FAO-56 tabulates Kc ini, Kc mid, and Kc end. Kc rises linearly through the development stage,
+/// so the midpoint of Kc ini and Kc mid is returned for it, and Kc end is returned for the late
+/// stage. Use a `GddKcCurve` when Kc should follow the crop through each stage.
+///
+/// # Arguments
+///
+/// * `stage` - The growth stage.
+/// * `crop` - The crop.
+///
+/// # Returns
+///
+/// * The crop coefficient for a sub-humid climate with a minimum RH of 45% and 2 m/s wind.
+pub fn kc_for_stage(stage: CropStage, crop: Crop) -> f64 {
+    // (Kc ini, Kc mid, Kc end) from FAO-56 Table 12
+    let (kc_ini, kc_mid, kc_end) = match crop {
+        Crop::Corn => (0.30, 1.20, 0.60),
+        Crop::Alfalfa => (0.40, 0.95, 0.90),
+        Crop::Wheat => (0.30, 1.15, 0.25),
+        Crop::Soybean => (0.40, 1.15, 0.50),
+    };
+
+    match stage {
+        CropStage::Initial => kc_ini,
+        CropStage::Development => (kc_ini + kc_mid) / 2.0,
+        CropStage::Mid => kc_mid,
+        CropStage::Late => kc_end,
+    }
+}
+
+/// Calculates crop evapotranspiration with the dual crop coefficient, ETc = (Kcb + Ke) * ETo
+/// (FAO-56 Eq. 69), which separates crop transpiration from soil evaporation.
+///
+/// # Arguments
+///
+/// * `eto` - Reference evapotranspiration in mm/day.
+/// * `kcb` - Basal crop coefficient for transpiration.
+/// * `ke` - Soil evaporation coefficient, see `evaporation_coefficient`.
+///
+/// # Returns
+///
+/// * The crop evapotranspiration in mm/day.
+pub fn dual_crop_et(eto: f64, kcb: f64, ke: f64) -> f64 {
+    (kcb + ke) * eto
+}
+
+/// Calculates the soil evaporation coefficient, Ke = min(Kr * (Kc max - Kcb), few * Kc max)
+/// (FAO-56 Eq. 71).
+///
+/// # Arguments
+///
+/// * `kc_max` - Upper limit on Kc following rain or irrigation, about 1.05 to 1.30.
+/// * `kcb` - Basal crop coefficient.
+/// * `few` - Fraction of the soil that is both exposed and wetted, from 0.01 to 1.
+/// * `kr` - Evaporation reduction coefficient from the drying of the surface layer, 1 while the
+///   surface is wet and falling to 0 as the readily evaporable water is used up.
+///
+/// # Returns
+///
+/// * The soil evaporation coefficient, never negative.
+pub fn evaporation_coefficient(kc_max: f64, kcb: f64, few: f64, kr: f64) -> f64 {
+    (kr * (kc_max - kcb)).min(few * kc_max).max(0.0)
+}
+
+/// Basal crop coefficient curve driven by accumulated growing degree days (GDD).
+///
+/// Each point pairs an accumulated GDD threshold with the Kc reached at that threshold, which
+/// lets Kc follow crop phenology instead of the calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GddKcCurve {
+    points: Vec<(f64, f64)>, // (accumulated GDD, Kc) sorted by GDD
+}
+
+impl GddKcCurve {
+    /// Creates a curve from (accumulated GDD, Kc) points.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Curve points with strictly increasing GDD thresholds.
+    ///
+    /// # Returns
+    ///
+    /// * The curve, or `DomainError` when there are no points or the thresholds are not strictly
+    ///   increasing.
+    pub fn new(points: Vec<(f64, f64)>) -> Result<GddKcCurve, RefEtError> {
+        if points.is_empty() {
+            return Err(RefEtError::DomainError(
+                "GDD Kc curve must have at least one point",
+            ));
+        }
+        if points.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(RefEtError::DomainError(
+                "GDD thresholds must be strictly increasing",
+            ));
+        }
+
+        Ok(GddKcCurve { points })
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+}
+
+/// Calculates the crop coefficient for the accumulated growing degree days.
+///
+/// Kc is linearly interpolated between the curve points and held at the first or last Kc
+/// outside the curve.
+///
+/// # Arguments
+///
+/// * `accumulated_gdd` - Growing degree days accumulated since planting.
+/// * `curve` - The GDD based Kc curve for the crop.
+///
+/// # Returns
+///
+/// * The crop coefficient.
+pub fn kc_from_gdd(accumulated_gdd: f64, curve: &GddKcCurve) -> f64 {
+    let points = curve.points();
+    let (first_gdd, first_kc) = points[0];
+    let (last_gdd, last_kc) = points[points.len() - 1];
+
+    if accumulated_gdd <= first_gdd {
+        return first_kc;
+    }
+    if accumulated_gdd >= last_gdd {
+        return last_kc;
+    }
+
+    for pair in points.windows(2) {
+        let (gdd_0, kc_0) = pair[0];
+        let (gdd_1, kc_1) = pair[1];
+        if accumulated_gdd <= gdd_1 {
+            return kc_0 + (kc_1 - kc_0) * (accumulated_gdd - gdd_0) / (gdd_1 - gdd_0);
+        }
+    }
+
+    last_kc
+}
+
+/// Calculates monthly effective rainfall with the USDA-SCS method (NEH Part 623, Chapter 2):
+/// Pe = SF * (0.70917 * P^0.82416 - 0.11556) * 10^(0.02426 * ETc), with P and ETc in inches.
+///
+/// The soil water storage factor SF is 1.0, which corresponds to the 75 mm (3 in) net irrigation
+/// depth the method was fitted for. Values are converted from and back to mm here.
+///
+/// # Arguments
+///
+/// * `monthly_rain_mm` - Total rainfall for the month in mm.
+/// * `monthly_etc_mm` - Total crop ET for the month in mm.
+///
+/// # Returns
+///
+/// * The effective rainfall for the month in mm, never more than the rainfall or the crop ET and
+///   never negative.
+pub fn effective_rainfall_scs(monthly_rain_mm: f64, monthly_etc_mm: f64) -> f64 {
+    const MM_PER_INCH: f64 = 25.4;
+    if monthly_rain_mm <= 0.0 {
+        return 0.0;
+    }
+
+    let rain = monthly_rain_mm / MM_PER_INCH;
+    let etc = monthly_etc_mm / MM_PER_INCH;
+    let effective = (0.70917 * rain.powf(0.82416) - 0.11556) * 10f64.powf(0.02426 * etc);
+
+    (effective * MM_PER_INCH)
+        .min(monthly_rain_mm)
+        .min(monthly_etc_mm)
+        .max(0.0)
+}
+
+/// Calculates the net irrigation requirement, the depth of water that must reach the root zone to
+/// replace crop ET not met by rainfall: ETc - Pe, or 0 when rainfall covers the crop ET.
+///
+/// # Arguments
+///
+/// * `etc` - Crop evapotranspiration for the period in mm.
+/// * `effective_rain` - Effective rainfall for the period in mm, such as from
+///   `effective_rainfall_scs`.
+///
+/// # Returns
+///
+/// * The net irrigation requirement in mm.
+pub fn net_irrigation_requirement(etc: f64, effective_rain: f64) -> f64 {
+    (etc - effective_rain).max(0.0)
+}
+
+/// Calculates the gross irrigation depth to apply, the net requirement divided by the
+/// application efficiency to cover the water lost in application: (ETc - Pe) / efficiency.
+///
+/// # Arguments
+///
+/// * `etc` - Crop evapotranspiration for the period in mm.
+/// * `effective_rain` - Effective rainfall for the period in mm, such as from
+///   `effective_rainfall_scs`.
+/// * `application_efficiency` - Fraction of the applied water that reaches the root zone, above 0
+///   and at most 1, such as 0.85 for a center pivot.
+///
+/// # Returns
+///
+/// * The irrigation depth to apply in mm, 0 when rainfall covers the crop ET, or `OutOfRange`
+///   when `application_efficiency` is not above 0 and at most 1.
+pub fn gross_irrigation_requirement(
+    etc: f64,
+    effective_rain: f64,
+    application_efficiency: f64,
+) -> Result<f64, RefEtError> {
+    if !(application_efficiency > 0.0 && application_efficiency <= 1.0) {
+        return Err(RefEtError::OutOfRange {
+            field: "application_efficiency",
+            value: application_efficiency,
+        });
+    }
+
+    Ok(net_irrigation_requirement(etc, effective_rain) / application_efficiency)
+}
+
+/// Running root zone water balance for irrigation scheduling.
+///
+/// Each day adds crop ET to the depletion and removes rainfall from it. Rain beyond field
+/// capacity drains away, so the deficit never drops below zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EtAccumulator {
+    total: f64,   // crop ET accumulated since the accumulator was created in mm
+    deficit: f64, // root zone depletion below field capacity in mm
+}
+
+impl EtAccumulator {
+    pub fn new() -> EtAccumulator {
+        EtAccumulator::default()
+    }
+
+    /// Adds a day of crop ET and rainfall, both in mm.
+    pub fn add_day(&mut self, etc: f64, rain: f64) {
+        self.total += etc;
+        self.deficit = (self.deficit + etc - rain).max(0.0);
+    }
+
+    /// Refills the root zone to field capacity after an irrigation event. The seasonal ET total is
+    /// kept.
+    pub fn reset(&mut self) {
+        self.deficit = 0.0;
+    }
+
+    pub fn deficit(&self) -> f64 {
+        self.deficit
+    }
+
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_water_stress_index() {
+        // Given
+        let eta = 3.0;
+        let eto = 5.0;
+
+        // When
+        let index = water_stress_index(eta, eto);
+
+        assert!((index - 0.6).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_water_stress_index_guards() {
+        assert_eq!(water_stress_index(6.0, 5.0), 1.0);
+        assert_eq!(water_stress_index(-0.5, 5.0), 0.0);
+        assert_eq!(water_stress_index(1.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn test_water_stress_index_batch() {
+        let result = water_stress_index_batch(&[3.0, 1.0], &[5.0, 4.0]);
+        assert_eq!(result.len(), 2);
+        assert!((result[0] - 0.6).abs() < 0.0001);
+        assert!((result[1] - 0.25).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_kc_for_stage_table_values() {
+        assert_eq!(kc_for_stage(CropStage::Mid, Crop::Corn), 1.20);
+        assert_eq!(kc_for_stage(CropStage::Initial, Crop::Soybean), 0.40);
+        assert_eq!(kc_for_stage(CropStage::Late, Crop::Alfalfa), 0.90);
+        assert!((kc_for_stage(CropStage::Development, Crop::Wheat) - 0.725).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_corn_mid_season_etc() {
+        // Given, the Greeley short reference day
+        let eto = 5.69;
+
+        // When
+        let etc = apply_crop_coefficient(eto, kc_for_stage(CropStage::Mid, Crop::Corn));
+
+        assert!((etc - 6.828).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_evaporation_coefficient() {
+        // Given, Kc max of 1.20 over an initial stage Kcb of 0.30
+        let kc_max = 1.20;
+        let kcb = 0.30;
+
+        // When
+        let wet = evaporation_coefficient(kc_max, kcb, 1.0, 1.0);
+        let partly_wetted = evaporation_coefficient(kc_max, kcb, 0.4, 1.0);
+        let drying = evaporation_coefficient(kc_max, kcb, 1.0, 0.5);
+
+        assert!((wet - 0.90).abs() < 0.0001);
+        assert!((partly_wetted - 0.48).abs() < 0.0001);
+        assert!((drying - 0.45).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_dual_crop_et() {
+        let ke = evaporation_coefficient(1.20, 0.30, 1.0, 1.0);
+
+        assert!((dual_crop_et(5.0, 0.30, ke) - 6.0).abs() < 0.0001);
+    }
+
+    fn corn_gdd_curve() -> GddKcCurve {
+        GddKcCurve::new(vec![
+            (0.0, 0.3),
+            (400.0, 0.3),
+            (1200.0, 1.2),
+            (2200.0, 1.2),
+            (2700.0, 0.6),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_kc_from_gdd_mid_curve() {
+        // Given
+        let curve = corn_gdd_curve();
+
+        // When
+        let kc = kc_from_gdd(800.0, &curve);
+
+        // halfway through development between 0.3 and 1.2
+        assert!((kc - 0.75).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_kc_from_gdd_outside_curve() {
+        let curve = corn_gdd_curve();
+
+        assert_eq!(kc_from_gdd(-10.0, &curve), 0.3);
+        assert_eq!(kc_from_gdd(1500.0, &curve), 1.2);
+        assert_eq!(kc_from_gdd(3000.0, &curve), 0.6);
+    }
+
+    #[test]
+    fn test_gdd_kc_curve_requires_increasing_gdd() {
+        assert!(GddKcCurve::new(vec![(0.0, 0.3), (400.0, 0.3), (400.0, 1.2)]).is_err());
+        assert!(GddKcCurve::new(vec![(500.0, 0.3), (400.0, 1.2)]).is_err());
+        assert!(GddKcCurve::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_effective_rainfall_scs() {
+        // Given, 4 in of rain in a month with 6 in of crop ET, in the inch units of USDA-SCS (1993),
+        // NEH Part 623, Chapter 2
+        let rain = 4.0 * 25.4;
+        let etc = 6.0 * 25.4;
+
+        // When
+        let effective = effective_rainfall_scs(rain, etc);
+
+        // the NEH equation in inches: (0.70917 * 4^0.82416 - 0.11556) * 10^(0.02426 * 6) = 2.947 in
+        assert!((effective / 25.4 - 2.947).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_effective_rainfall_scs_caps() {
+        assert!((effective_rainfall_scs(20.0, 200.0) - 18.41).abs() < 0.01);
+        assert_eq!(effective_rainfall_scs(150.0, 40.0), 40.0);
+        assert_eq!(effective_rainfall_scs(0.0, 150.0), 0.0);
+        assert_eq!(effective_rainfall_scs(0.1, 10.0), 0.0);
+    }
+
+    #[test]
+    fn test_net_irrigation_requirement_deficit() {
+        // Given, a week of 42 mm crop ET with 8.5 mm of effective rain
+        let etc = 42.0;
+        let effective_rain = 8.5;
+
+        // When
+        let depth = net_irrigation_requirement(etc, effective_rain);
+
+        assert!((depth - 33.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_net_irrigation_requirement_rain_surplus() {
+        assert_eq!(net_irrigation_requirement(20.0, 35.0), 0.0);
+    }
+
+    #[test]
+    fn test_gross_irrigation_requirement_deficit() {
+        // Given, a week of 42 mm crop ET with 8.5 mm of effective rain under a center pivot
+        let etc = 42.0;
+        let effective_rain = 8.5;
+
+        // When
+        let depth = gross_irrigation_requirement(etc, effective_rain, 0.85).unwrap();
+
+        assert!((depth - 39.4118).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_gross_irrigation_requirement_rain_surplus() {
+        assert_eq!(gross_irrigation_requirement(20.0, 35.0, 0.85), Ok(0.0));
+    }
+
+    #[test]
+    fn test_gross_irrigation_requirement_bad_efficiency() {
+        assert_eq!(
+            gross_irrigation_requirement(42.0, 8.5, 0.0),
+            Err(RefEtError::OutOfRange {
+                field: "application_efficiency",
+                value: 0.0
+            })
+        );
+        assert!(gross_irrigation_requirement(42.0, 8.5, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_et_accumulator_week() {
+        // Given, a week of ETc with a 12 mm storm on day 4
+        let days = [
+            (5.0, 0.0),
+            (5.5, 0.0),
+            (6.0, 0.0),
+            (4.0, 12.0),
+            (5.0, 0.0),
+            (6.5, 0.0),
+            (6.0, 2.0),
+        ];
+        let mut accumulator = EtAccumulator::new();
+
+        // When
+        for (etc, rain) in days {
+            accumulator.add_day(etc, rain);
+        }
+
+        assert!((accumulator.total() - 38.0).abs() < 1e-9);
+        assert!((accumulator.deficit() - 24.0).abs() < 1e-9);
+
+        accumulator.reset();
+        assert_eq!(accumulator.deficit(), 0.0);
+        assert!((accumulator.total() - 38.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_et_accumulator_clamps_at_field_capacity() {
+        let mut accumulator = EtAccumulator::new();
+
+        accumulator.add_day(5.0, 0.0);
+        accumulator.add_day(3.0, 40.0);
+
+        assert_eq!(accumulator.deficit(), 0.0);
+        accumulator.add_day(4.0, 0.0);
+        assert_eq!(accumulator.deficit(), 4.0);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{Input, RefEtError, Value};
+use chrono::NaiveDate;
+use csv::StringRecord;
+use std::io::Read;
+
+/// Names the CSV header of each input and the units the column is recorded in.
+///
+/// Only the date and temperatures are required. Optional columns that are `None` are not read,
+/// and an empty cell in a mapped column is treated as a missing value for that day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMapping {
+    pub date: String,              // header of the date column
+    pub date_format: String,       // chrono format of the dates, "%Y-%m-%d" by default
+    pub tmax: String,              // header of the daily maximum temperature
+    pub tmin: String,              // header of the daily minimum temperature
+    pub temperature_units: String, // "C", "F", or "K" for tmax, tmin, and dewpoint
+    pub ea: Option<String>,        // header of the actual vapor pressure
+    pub ea_units: String,          // "kPa" or "Pa"
+    pub dewpoint: Option<String>,  // header of the dewpoint temperature
+    pub rhmax: Option<String>,     // header of the daily maximum relative humidity in %
+    pub rhmin: Option<String>,     // header of the daily minimum relative humidity in %
+    pub rs: Option<String>,        // header of the solar radiation
+    pub rs_units: String,          // "MJ/m2/day", "W/m2" as a daily mean, or "ly" for langleys
+    pub ws: Option<String>,        // header of the wind speed
+    pub ws_units: String,          // "m/s", "mph", or "km/h"
+    pub wind_height: f64,          // height of the wind measurement in meters
+    pub z: Option<String>,         // header of the station elevation in meters
+    pub latitude: Option<String>,  // header of the station latitude in decimal degrees
+    pub skip_invalid_rows: bool,   // skip rows that fail to parse instead of returning the error
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        ColumnMapping {
+            date: "date".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            tmax: "tmax".to_string(),
+            tmin: "tmin".to_string(),
+            temperature_units: "C".to_string(),
+            ea: None,
+            ea_units: "kPa".to_string(),
+            dewpoint: None,
+            rhmax: None,
+            rhmin: None,
+            rs: None,
+            rs_units: "MJ/m2/day".to_string(),
+            ws: None,
+            ws_units: "m/s".to_string(),
+            wind_height: 2.0,
+            z: None,
+            latitude: None,
+            skip_invalid_rows: false,
+        }
+    }
+}
+
+/// Reads daily station records from CSV into inputs.
+///
+/// Station values such as elevation and latitude often aren't columns, in which case they can be
+/// set on each input afterwards with `set_z` and `set_latitude`.
+///
+/// # Arguments
+///
+/// * `r` - The CSV data, with a header row.
+/// * `mapping` - The headers and units of each input.
+///
+/// # Returns
+///
+/// * One input per row in file order, or the first error when a mapped header is missing or a
+///   row can't be parsed. Rows that can't be parsed are left out instead when
+///   `skip_invalid_rows` is set.
+pub fn read_inputs_csv<R: Read>(r: R, mapping: &ColumnMapping) -> Result<Vec<Input>, RefEtError> {
+    let mut reader = csv::Reader::from_reader(r);
+    let headers = reader
+        .headers()
+        .map_err(|_| RefEtError::DomainError("csv header row could not be read"))?
+        .clone();
+    let columns = Columns::locate(&headers, mapping)?;
+
+    let mut inputs = Vec::new();
+    for record in reader.records() {
+        let input = record
+            .map_err(|_| RefEtError::DomainError("csv row could not be read"))
+            .and_then(|record| columns.input(&record, mapping));
+
+        match input {
+            Ok(input) => inputs.push(input),
+            Err(_) if mapping.skip_invalid_rows => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Positions of the mapped columns in the header row.
+struct Columns {
+    date: usize,
+    tmax: usize,
+    tmin: usize,
+    ea: Option<usize>,
+    dewpoint: Option<usize>,
+    rhmax: Option<usize>,
+    rhmin: Option<usize>,
+    rs: Option<usize>,
+    ws: Option<usize>,
+    z: Option<usize>,
+    latitude: Option<usize>,
+}
+
+impl Columns {
+    fn locate(headers: &StringRecord, mapping: &ColumnMapping) -> Result<Columns, RefEtError> {
+        let find = |name: &str, field: &'static str| {
+            headers
+                .iter()
+                .position(|header| header.trim() == name)
+                .ok_or(RefEtError::MissingField(field))
+        };
+        let find_optional = |name: &Option<String>, field: &'static str| {
+            name.as_deref().map(|name| find(name, field)).transpose()
+        };
+
+        Ok(Columns {
+            date: find(&mapping.date, "date")?,
+            tmax: find(&mapping.tmax, "tmax")?,
+            tmin: find(&mapping.tmin, "tmin")?,
+            ea: find_optional(&mapping.ea, "ea")?,
+            dewpoint: find_optional(&mapping.dewpoint, "dewpoint")?,
+            rhmax: find_optional(&mapping.rhmax, "rhmax")?,
+            rhmin: find_optional(&mapping.rhmin, "rhmin")?,
+            rs: find_optional(&mapping.rs, "rs")?,
+            ws: find_optional(&mapping.ws, "ws")?,
+            z: find_optional(&mapping.z, "z")?,
+            latitude: find_optional(&mapping.latitude, "latitude")?,
+        })
+    }
+
+    fn input(&self, record: &StringRecord, mapping: &ColumnMapping) -> Result<Input, RefEtError> {
+        let date = record
+            .get(self.date)
+            .and_then(|date| NaiveDate::parse_from_str(date.trim(), &mapping.date_format).ok())
+            .ok_or(RefEtError::DomainError(
+                "csv date doesn't match the date format",
+            ))?;
+        let tmax = number(record, Some(self.tmax))?.ok_or(RefEtError::MissingField("tmax"))?;
+        let tmin = number(record, Some(self.tmin))?.ok_or(RefEtError::MissingField("tmin"))?;
+        let temperature_units = mapping.temperature_units.as_str();
+
+        let mut input = Input::new(
+            Value::new(tmax, temperature_units),
+            Value::new(tmin, temperature_units),
+            date,
+        )?;
+
+        if let Some(ea) = number(record, self.ea)? {
+            input.set_ea(Value::new(ea, &mapping.ea_units))?;
+        }
+        if let Some(dewpoint) = number(record, self.dewpoint)? {
+            input.set_dewpoint(Value::new(dewpoint, temperature_units))?;
+        }
+        // a single humidity column is kept for the Eq. 12 and 13 methods
+        match (number(record, self.rhmax)?, number(record, self.rhmin)?) {
+            (Some(rhmax), Some(rhmin)) => {
+                input.set_rh(rhmax, rhmin)?;
+            }
+            (Some(rhmax), None) => {
+                input.set_rhmax(rhmax)?;
+            }
+            (None, Some(rhmin)) => {
+                input.set_rhmin(rhmin)?;
+            }
+            (None, None) => {}
+        }
+        if let Some(rs) = number(record, self.rs)? {
+            input.set_rs(Value::new(rs, &mapping.rs_units).to("MJ/m2/day")?)?;
+        }
+        if let Some(ws) = number(record, self.ws)? {
+            let ws = Value::new(ws, &mapping.ws_units).to("m/s")?;
+            input.set_ws(ws, mapping.wind_height)?;
+        }
+        if let Some(z) = number(record, self.z)? {
+            input.set_z(z)?;
+        }
+        if let Some(latitude) = number(record, self.latitude)? {
+            input.set_latitude(latitude.to_radians())?;
+        }
+
+        Ok(input)
+    }
+}
+
+/// Reads the number in the column, `None` when the column isn't mapped or the cell is empty.
+fn number(record: &StringRecord, column: Option<usize>) -> Result<Option<f64>, RefEtError> {
+    match column.and_then(|column| record.get(column)).map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(cell) => cell
+            .parse::<f64>()
+            .map(Some)
+            .map_err(|_| RefEtError::DomainError("csv value is not a number")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_ref_et_input;
+
+    const GREELEY_CSV: &str = "\
+date,tmax,tmin,ea,rs,ws
+2000-07-01,32.4,10.9,1.27,22.4,1.94
+2000-07-02,31.0,12.5,,21.0,2.5
+";
+
+    fn greeley_mapping() -> ColumnMapping {
+        ColumnMapping {
+            ea: Some("ea".to_string()),
+            rs: Some("rs".to_string()),
+            ws: Some("ws".to_string()),
+            wind_height: 3.0,
+            ..ColumnMapping::default()
+        }
+    }
+
+    #[test]
+    fn test_read_inputs_csv_greeley() {
+        // Given
+        let mapping = greeley_mapping();
+
+        // When
+        let mut inputs = read_inputs_csv(GREELEY_CSV.as_bytes(), &mapping).unwrap();
+
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[1].ea(), None);
+        inputs[0]
+            .set_z(1462.4)
+            .and_then(|input| input.set_latitude(40.41_f64.to_radians()))
+            .unwrap();
+        let (short, tall) = calculate_ref_et_input(&inputs[0]).unwrap();
+        assert!((short - 5.69).abs() < 0.01);
+        assert!((tall - 7.32).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_read_inputs_csv_invalid_rows() {
+        // Given
+        let data = format!("{}2000-07-03,hot,12.0,1.2,20.0,2.0\n", GREELEY_CSV);
+        let skipping = ColumnMapping {
+            skip_invalid_rows: true,
+            ..greeley_mapping()
+        };
+
+        // When
+        let strict = read_inputs_csv(data.as_bytes(), &greeley_mapping());
+        let skipped = read_inputs_csv(data.as_bytes(), &skipping);
+
+        assert!(strict.is_err());
+        assert_eq!(skipped.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_read_inputs_csv_single_rh_column() {
+        // Given, a station that only logs the afternoon minimum humidity
+        let data = "\
+date,tmax,tmin,rh_min,rs,ws
+2000-07-01,32.4,10.9,30.0,22.4,1.94
+";
+        let mapping = ColumnMapping {
+            ea: None,
+            rhmin: Some("rh_min".to_string()),
+            ..greeley_mapping()
+        };
+
+        // When
+        let inputs = read_inputs_csv(data.as_bytes(), &mapping).unwrap();
+
+        assert_eq!(inputs[0].rhmin(), Some(30.0));
+        assert_eq!(inputs[0].rhmax(), None);
+        assert!(!inputs[0].missing_fields().contains(&"ea"));
+    }
+
+    #[test]
+    fn test_read_inputs_csv_missing_header() {
+        let mapping = ColumnMapping {
+            rhmax: Some("rh_max".to_string()),
+            ..greeley_mapping()
+        };
+
+        let result = read_inputs_csv(GREELEY_CSV.as_bytes(), &mapping);
+
+        assert_eq!(result.err(), Some(RefEtError::MissingField("rhmax")));
+    }
+}
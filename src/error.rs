@@ -0,0 +1,26 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors returned by the reference ET calculations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefEtError {
+    MissingField(&'static str), // a required input was not supplied
+    InvalidUnits(String),       // the units string is not recognized for the field
+    OutOfRange { field: &'static str, value: f64 }, // the input is outside its physical range
+    DomainError(&'static str),  // the calculation is undefined for the supplied inputs
+}
+
+impl fmt::Display for RefEtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefEtError::MissingField(field) => write!(f, "missing required field: {}", field),
+            RefEtError::InvalidUnits(units) => write!(f, "invalid units: {}", units),
+            RefEtError::OutOfRange { field, value } => {
+                write!(f, "{} is out of range: {}", field, value)
+            }
+            RefEtError::DomainError(message) => write!(f, "domain error: {}", message),
+        }
+    }
+}
+
+impl Error for RefEtError {}
@@ -1,7 +1,13 @@
-use crate::conversions::day_of_year;
-use crate::EaInput;
+use crate::asce;
+use crate::config::{EtCoefficients, RefEtConfig};
+use crate::conversions::{day_of_year, f_to_c, k_to_c};
+use crate::{EaInput, RefEtError};
+use chrono::{DateTime, Datelike, Utc};
 use climate::output::Output;
-use std::f64::consts::{E, PI};
+use climate::units::Units;
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fmt;
 
 /// Calculates the short and tall referece et for a given set of conditions.
 ///
@@ -11,107 +17,628 @@ use std::f64::consts::{E, PI};
 ///
 /// # Returns
 ///
-/// * a tuple containing the short and tall reference evapotranspiration.
-pub fn calculate_ref_et(input: &Output) -> (f64, f64) {
-    const LAMDA: f64 = 0.408;
-    const G: f64 = 0.0;
-    let eta = EaInput::new_from_output(input); // Creates an EaInput from the Input values, chooses the proper method based on the input data.
+/// * The short and tall reference evapotranspiration, or an error when wind speed is missing, ea
+///   can't be calculated from the humidity inputs, or Rs is negative.
+pub fn calculate_ref_et(input: &Output) -> Result<RefEt, RefEtError> {
+    calculate_ref_et_detailed(input).map(|components| RefEt {
+        short: components.short,
+        tall: components.tall,
+    })
+}
 
-    // atmospheric pressure
-    let atmospheric_pressure = calc_atmospheric_pressure(input.get_z());
-    // println!("Atmospheric pressure: {}", atmospheric_pressure);
+/// The short and tall reference ET of a day in mm/day, labeled so they can't be swapped.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefEt {
+    pub short: f64, // short grass reference, ETos
+    pub tall: f64,  // tall alfalfa reference, ETrs
+}
 
-    // psycometric constant
-    let gamma = psy_constant(atmospheric_pressure);
-    // println!("Psycometric constant: {}", gamma);
+impl From<RefEt> for (f64, f64) {
+    fn from(et: RefEt) -> (f64, f64) {
+        (et.short, et.tall)
+    }
+}
 
-    // mean temperature
-    let mean_temperature = mean_temp(input.get_tmax(), input.get_tmin());
-    // println!("Mean temperature: {}", mean_temperature);
+impl From<(f64, f64)> for RefEt {
+    fn from((short, tall): (f64, f64)) -> RefEt {
+        RefEt { short, tall }
+    }
+}
 
-    // slope of vapor pressure curve
-    let delta = es_slope(mean_temperature);
-    // println!("Slope of vapor pressure curve: {}", delta);
+impl fmt::Display for RefEt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ETos = {:.2} mm/day, ETrs = {:.2} mm/day",
+            self.short, self.tall
+        )
+    }
+}
 
-    // saturation vapor pressure
-    let saturation_vapor_pressure = es(input.get_tmax(), input.get_tmin());
-    // println!("Saturation vapor pressure: {}", saturation_vapor_pressure);
+/// The reference crop of the ASCE Standardized equation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceCrop {
+    ShortGrass,  // clipped grass, ETos
+    TallAlfalfa, // full cover alfalfa, ETrs
+}
 
-    // extraterrestrial radiation
-    let extraterrestrial_radiation = calc_ra(
-        input.get_latitude(),
-        day_of_year(&input.get_date()).unwrap(),
-    );
-    // println!("Latitude: {}", input.get_latitude());
-    // println!("Day of Year: {}", day_of_year(&input.get_date()).unwrap());
-    // println!("Extraterrestrial radiation: {}", extraterrestrial_radiation);
+/// Calculates the reference et for one reference crop.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `crop` - The reference crop to return.
+///
+/// # Returns
+///
+/// * The reference evapotranspiration for `crop` in mm/day, or an error when wind speed is
+///   missing or ea can't be calculated from the humidity inputs.
+pub fn calculate_single_ref_et(input: &Output, crop: ReferenceCrop) -> Result<f64, RefEtError> {
+    let components = calculate_ref_et_detailed(input)?;
 
-    // clear sky radiation
-    let clear_sky_radiation = calc_rso(extraterrestrial_radiation, input.get_z());
-    // println!("Clear sky radiation: {}", clear_sky_radiation);
+    Ok(match crop {
+        ReferenceCrop::ShortGrass => components.short,
+        ReferenceCrop::TallAlfalfa => components.tall,
+    })
+}
 
-    let rs: f64;
-    if let Some(rs_value) = input.get_rs() {
-        rs = rs_value;
-    } else {
-        let harg_rs = calculate_hargreaves_samani_rs(
-            input.get_tmax(),
-            input.get_tmin(),
-            extraterrestrial_radiation,
-        );
-        // limit rs to clear sky radiation
-        if harg_rs > clear_sky_radiation {
-            rs = clear_sky_radiation;
-        } else {
-            rs = harg_rs;
+/// Calculates the vapor pressure deficit, es - ea, for the day.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature and relative humidity.
+///
+/// # Returns
+///
+/// * The vapor pressure deficit in kPa, or an error when ea can't be calculated from the humidity
+///   inputs.
+pub fn vapor_pressure_deficit(input: &Output) -> Result<f64, RefEtError> {
+    let config = RefEtConfig::default();
+    // wind isn't part of the deficit, so any placeholder keeps a calm record usable
+    let values = DailyValues::from_output_with_default_wind(input, &config, Some(0.0))?;
+
+    Ok(es(values.tmax, values.tmin) - values.ea)
+}
+
+/// Units a reference ET value can be reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EtUnits {
+    MmPerDay,   // millimeters per day, the units of the ASCE Standardized equation
+    InchPerDay, // inches per day, common for US irrigation districts
+    MmPerHour,  // the daily total spread evenly over 24 hours
+}
+
+impl EtUnits {
+    /// Converts a reference ET value in mm/day to these units.
+    pub fn from_mm_per_day(self, value: f64) -> f64 {
+        match self {
+            EtUnits::MmPerDay => value,
+            EtUnits::InchPerDay => value / 25.4,
+            EtUnits::MmPerHour => value / 24.0,
         }
+    }
+}
+
+/// Calculates the short and tall reference et in the requested units.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `units` - The units of the returned values.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration, or an error when wind
+///   speed is missing or ea can't be calculated from the humidity inputs.
+pub fn calculate_ref_et_in(input: &Output, units: EtUnits) -> Result<(f64, f64), RefEtError> {
+    let RefEt { short, tall } = calculate_ref_et(input)?;
+    Ok((units.from_mm_per_day(short), units.from_mm_per_day(tall)))
+}
+
+/// Calculates the short and tall reference et using the supplied configuration.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `config` - The configuration for the calculation, such as the Tmin dewpoint offset.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration, or an error when a
+///   required input is missing or invalid.
+pub fn calculate_ref_et_with_config(
+    input: &Output,
+    config: &RefEtConfig,
+) -> Result<(f64, f64), RefEtError> {
+    let values = DailyValues::from_output(input, config)?;
+    Ok(ref_et_from_values(&values, config))
+}
+
+/// Calculates the short and tall reference et with calibrated Cn and Cd constants in place of the
+/// ASCE Standardized values.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `coeffs` - The numerator and denominator constants for the short and tall reference crops.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration, or an error when a
+///   required input is missing or invalid.
+pub fn calculate_ref_et_with_coeffs(
+    input: &Output,
+    coeffs: &EtCoefficients,
+) -> Result<(f64, f64), RefEtError> {
+    let config = RefEtConfig {
+        cn_short: coeffs.cn_short,
+        cd_short: coeffs.cd_short,
+        cn_tall: coeffs.cn_tall,
+        cd_tall: coeffs.cd_tall,
+        alfalfa_wind_profile: coeffs.alfalfa_wind_profile,
+        ..RefEtConfig::default()
     };
+    calculate_ref_et_with_config(input, &config)
+}
 
-    // fraction of clear day
-    let fraction_of_clear_day = calc_fcd(clear_sky_radiation, rs);
-    // println!("Fraction of clear day: {}", fraction_of_clear_day);
+/// Daily weather and site values for the ASCE Standardized equation after the ea method has been
+/// chosen and every unit converted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DailyValues {
+    pub(crate) tmax: f64,             // daily maximum air temperature in Celsius
+    pub(crate) tmin: f64,             // daily minimum air temperature in Celsius
+    pub(crate) ea: f64,               // actual vapor pressure in kPa
+    pub(crate) rs: Option<f64>,       // measured solar radiation in MJ/m2/day, estimated when None
+    pub(crate) rn: Option<f64>,       // measured net radiation in MJ/m2/day, from Rs when None
+    pub(crate) sunshine: Option<f64>, // hours of bright sunshine, used when Rs is missing
+    pub(crate) cloud: Option<f64>,    // cloud cover fraction, used when Rs and sunshine are missing
+    pub(crate) ws: f64,               // wind speed in m/s measured at wz
+    pub(crate) wz: f64,               // wind measurement height in meters
+    pub(crate) z: f64,                // station elevation in meters
+    pub(crate) pressure: Option<f64>, // measured pressure in kPa, estimated from z when None
+    pub(crate) latitude: f64,         // station latitude in radians
+    pub(crate) doy: u32,              // day of the year
+    pub(crate) g: f64,                // soil heat flux density in MJ/m2/day, 0 for daily periods
+}
 
-    // long-wave radiation
-    let long_wave_radiation = calc_rnl(
-        fraction_of_clear_day,
-        eta.ea().unwrap(),
-        input.get_tmax(),
-        input.get_tmin(),
-    );
-    // println!("Long-wave radiation: {}", long_wave_radiation);
-
-    // short-wave radiation
-    let short_wave_radiation = calc_rns(rs);
-    // println!("Short-wave radiation: {}", short_wave_radiation);
-
-    let net_radiation = calc_rn(short_wave_radiation, long_wave_radiation);
-    // println!("Net radiation: {}", net_radiation);
-
-    let adjusted_wind_speed = calc_ws(input.get_ws().unwrap_or(0.0), input.get_wz());
-    // println!("Adjusted wind speed: {}", adjusted_wind_speed);
-
-    let et_short_numerator = LAMDA * delta * (net_radiation - G)
-        + gamma
-            * (900.0 / (mean_temperature + 273.0))
-            * adjusted_wind_speed
-            * (saturation_vapor_pressure - input.get_ea().unwrap());
-    let et_short_denominator = delta + gamma * (1.0 + 0.34 * adjusted_wind_speed);
-    // println!("ET short-term numerator: {}", et_short_numerator);
-    // println!("ET short-term denominator: {}", et_short_denominator);
-
-    let et_tall_numerator = LAMDA * delta * (net_radiation - G)
-        + gamma
-            * (1600.0 / (mean_temperature + 273.0))
-            * adjusted_wind_speed
-            * (saturation_vapor_pressure - input.get_ea().unwrap());
-    let et_tall_denominator = delta + gamma * (1.0 + 0.38 * adjusted_wind_speed);
-    // println!("ET tall-term numerator: {}", et_tall_numerator);
-    // println!("ET tall-term denominator: {}", et_tall_denominator);
-
-    (
-        et_short_numerator / et_short_denominator,
-        et_tall_numerator / et_tall_denominator,
-    )
+impl DailyValues {
+    pub(crate) fn from_output(
+        input: &Output,
+        config: &RefEtConfig,
+    ) -> Result<DailyValues, RefEtError> {
+        DailyValues::from_output_with_default_wind(input, config, None)
+    }
+
+    /// The measured atmospheric pressure in kPa, or the estimate from elevation (Eq. 3).
+    pub(crate) fn pressure(&self) -> f64 {
+        self.pressure
+            .unwrap_or_else(|| calc_atmospheric_pressure(self.z))
+    }
+
+    /// Resolves the output like `from_output`, using `default_ws` in m/s at 2 m when the output
+    /// has no wind speed instead of returning an error.
+    pub(crate) fn from_output_with_default_wind(
+        input: &Output,
+        config: &RefEtConfig,
+        default_ws: Option<f64>,
+    ) -> Result<DailyValues, RefEtError> {
+        let (ws, wz) = match (input.get_ws(), default_ws) {
+            (Some(ws), _) => (ws, input.get_wz()),
+            (None, Some(default_ws)) => (default_ws, 2.0),
+            (None, None) => return Err(RefEtError::MissingField("ws")),
+        };
+
+        // Creates an EaInput from the Input values, chooses the proper method based on the input data.
+        let eta = EaInput::new_from_output_with_config(input, config)?;
+        let ea = eta.ea()?;
+        let doy = day_of_year(&input.get_date()).map_err(|_| RefEtError::MissingField("date"))?;
+        check_rs(input.get_rs())?;
+
+        Ok(DailyValues {
+            tmax: to_celsius(input.get_tmax(), &config.temperature_units)?,
+            tmin: to_celsius(input.get_tmin(), &config.temperature_units)?,
+            ea,
+            rs: input.get_rs(),
+            rn: None,
+            sunshine: None,
+            cloud: None,
+            ws,
+            wz,
+            z: input.get_z(),
+            pressure: None,
+            latitude: input.get_latitude(),
+            doy,
+            g: 0.0,
+        })
+    }
+}
+
+/// Checks that a measured solar radiation isn't negative, such as from a pyranometer offset error.
+/// An Rs of 0, as on a polar night, is allowed and gives no net short-wave radiation.
+pub(crate) fn check_rs(rs: Option<f64>) -> Result<(), RefEtError> {
+    match rs {
+        Some(rs) if rs.is_nan() || rs < 0.0 => Err(RefEtError::OutOfRange {
+            field: "rs",
+            value: rs,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Abbreviations recognized as Kelvin, which the climate units don't cover.
+const KELVIN_ABBREVIATIONS: [&str; 2] = ["k", "kelvin"];
+
+/// Converts a temperature in the given units, "C", "F", or "K", to Celsius.
+pub(crate) fn to_celsius(value: f64, units: &str) -> Result<f64, RefEtError> {
+    if KELVIN_ABBREVIATIONS.contains(&units.trim().to_lowercase().as_str()) {
+        return Ok(k_to_c(value));
+    }
+
+    match Units::from_abbreviation(units) {
+        Ok(Units::Celsius) => Ok(value),
+        Ok(Units::Fahrenheit) => Ok(f_to_c(value)),
+        _ => Err(RefEtError::InvalidUnits(units.to_string())),
+    }
+}
+
+/// Every intermediate term of the ASCE Standardized daily calculation along with both reference ET
+/// values, for checking results against spreadsheets and logging the energy balance.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefEtComponents {
+    pub pressure: f64,        // atmospheric pressure in kPa
+    pub gamma: f64,           // psychrometric constant in kPa/C
+    pub tmean: f64,           // mean daily air temperature in Celsius
+    pub delta: f64,           // slope of the saturation vapor pressure curve in kPa/C
+    pub es: f64,              // saturation vapor pressure in kPa
+    pub ea: f64,              // actual vapor pressure in kPa
+    pub vpd: f64,             // vapor pressure deficit es - ea in kPa
+    pub ra: f64,              // extraterrestrial radiation in MJ/m2/day
+    pub rso: f64,             // clear sky radiation in MJ/m2/day
+    pub rs: f64,              // solar radiation used, measured or estimated, in MJ/m2/day
+    pub fcd: f64,             // cloudiness function
+    pub rns: f64,             // net short-wave radiation in MJ/m2/day
+    pub rnl: f64,             // net long-wave radiation in MJ/m2/day
+    pub rn: f64,              // net radiation in MJ/m2/day
+    pub u2: f64,              // wind speed at 2 m in m/s
+    pub short: f64,           // short (grass) reference ET in mm/day
+    pub tall: f64,            // tall (alfalfa) reference ET in mm/day
+    pub wind_defaulted: bool, // wind was missing and a default u2 was used
+}
+
+/// Field name, key used by `to_flat`, symbol as printed by `Display`, and unit of each component,
+/// in field order.
+const COMPONENTS: [(&str, &str, &str, &str); 18] = [
+    ("pressure", "atmospheric_pressure", "P", "kPa"),
+    ("gamma", "psychrometric_constant", "γ", "kPa/°C"),
+    ("tmean", "mean_temperature", "Tmean", "°C"),
+    ("delta", "saturation_slope", "Δ", "kPa/°C"),
+    ("es", "saturation_vapor_pressure", "es", "kPa"),
+    ("ea", "actual_vapor_pressure", "ea", "kPa"),
+    ("vpd", "vapor_pressure_deficit", "VPD", "kPa"),
+    ("ra", "extraterrestrial_radiation", "Ra", "MJ/m²/day"),
+    ("rso", "clear_sky_radiation", "Rso", "MJ/m²/day"),
+    ("rs", "solar_radiation", "Rs", "MJ/m²/day"),
+    ("fcd", "cloudiness_function", "fcd", ""),
+    ("rns", "net_shortwave_radiation", "Rns", "MJ/m²/day"),
+    ("rnl", "net_longwave_radiation", "Rnl", "MJ/m²/day"),
+    ("rn", "net_radiation", "Rn", "MJ/m²/day"),
+    ("u2", "wind_speed_2m", "u2", "m/s"),
+    ("short", "reference_et_short", "ETos", "mm/day"),
+    ("tall", "reference_et_tall", "ETrs", "mm/day"),
+    ("wind_defaulted", "wind_defaulted", "wind defaulted", ""),
+];
+
+const COMPONENT_UNITS: [(&str, &str); 18] = {
+    let mut units = [("", ""); 18];
+    let mut i = 0;
+    while i < COMPONENTS.len() {
+        units[i] = (COMPONENTS[i].0, COMPONENTS[i].3);
+        i += 1;
+    }
+    units
+};
+
+impl RefEtComponents {
+    /// The unit of each field by field name, in field order. Unitless fields have an empty unit.
+    pub fn units() -> &'static [(&'static str, &'static str)] {
+        &COMPONENT_UNITS
+    }
+
+    /// Flattens the components into descriptive snake_case keys such as `net_radiation` and
+    /// `reference_et_short` for tabular export, with `wind_defaulted` as 1 or 0.
+    pub fn to_flat(&self) -> BTreeMap<String, f64> {
+        COMPONENTS
+            .iter()
+            .zip(self.values())
+            .map(|(&(_, key, _, _), value)| (key.to_string(), value))
+            .collect()
+    }
+
+    /// The value of every field in field order, with `wind_defaulted` as 1 or 0.
+    fn values(&self) -> [f64; 18] {
+        [
+            self.pressure,
+            self.gamma,
+            self.tmean,
+            self.delta,
+            self.es,
+            self.ea,
+            self.vpd,
+            self.ra,
+            self.rso,
+            self.rs,
+            self.fcd,
+            self.rns,
+            self.rnl,
+            self.rn,
+            self.u2,
+            self.short,
+            self.tall,
+            if self.wind_defaulted { 1.0 } else { 0.0 },
+        ]
+    }
+}
+
+impl fmt::Display for RefEtComponents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (&(_, _, symbol, unit), value) in COMPONENTS[..17].iter().zip(self.values()) {
+            writeln!(f, "{symbol}: {value:.3} {unit}")?;
+        }
+        write!(f, "{}: {}", COMPONENTS[17].2, self.wind_defaulted)
+    }
+}
+
+/// Calculates the short and tall reference et along with every intermediate term.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+///
+/// # Returns
+///
+/// * The components of the calculation, or an error when wind speed is missing or ea can't be
+///   calculated from the humidity inputs.
+pub fn calculate_ref_et_detailed(input: &Output) -> Result<RefEtComponents, RefEtError> {
+    let config = RefEtConfig::default();
+    let values = DailyValues::from_output(input, &config)?;
+    Ok(components_from_values(&values, &config))
+}
+
+/// Calculates the short and tall reference et along with every intermediate term, using a
+/// measured soil heat flux in place of the daily assumption that G is 0.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `g` - Soil heat flux density in MJ/m2/day from heat-flux plates, 0 when None.
+///
+/// # Returns
+///
+/// * The components of the calculation, or an error when wind speed is missing or ea can't be
+///   calculated from the humidity inputs.
+pub fn calculate_ref_et_detailed_with_g(
+    input: &Output,
+    g: Option<f64>,
+) -> Result<RefEtComponents, RefEtError> {
+    let config = RefEtConfig::default();
+    let values = DailyValues {
+        g: g.unwrap_or(0.0),
+        ..DailyValues::from_output(input, &config)?
+    };
+    Ok(components_from_values(&values, &config))
+}
+
+/// Calculates the short and tall reference et along with every intermediate term, substituting
+/// `default_u2` when the output has no wind speed, as FAO-56 recommends 2 m/s where wind data is
+/// unavailable.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `default_u2` - Wind speed at 2 m in m/s used when wind is missing, typically 2.0.
+///
+/// # Returns
+///
+/// * The components of the calculation with `wind_defaulted` set when the default was used, or an
+///   error when the default is negative or ea can't be calculated from the humidity inputs.
+pub fn calculate_ref_et_with_defaults(
+    input: &Output,
+    default_u2: f64,
+) -> Result<RefEtComponents, RefEtError> {
+    if default_u2.is_nan() || default_u2 < 0.0 {
+        return Err(RefEtError::OutOfRange {
+            field: "default_u2",
+            value: default_u2,
+        });
+    }
+
+    let config = RefEtConfig::default();
+    let values = DailyValues::from_output_with_default_wind(input, &config, Some(default_u2))?;
+
+    Ok(RefEtComponents {
+        wind_defaulted: input.get_ws().is_none(),
+        ..components_from_values(&values, &config)
+    })
+}
+
+/// Calculates the short and tall reference et along with every intermediate term, using `albedo`
+/// in place of the 0.23 grass reference albedo for the net short-wave radiation.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `albedo` - Surface albedo between 0 and 1, such as 0.08 for open water.
+///
+/// # Returns
+///
+/// * The components of the calculation, or `OutOfRange` when the albedo is outside 0 to 1.
+pub fn calculate_ref_et_detailed_with_albedo(
+    input: &Output,
+    albedo: f64,
+) -> Result<RefEtComponents, RefEtError> {
+    if !(0.0..=1.0).contains(&albedo) {
+        return Err(RefEtError::OutOfRange {
+            field: "albedo",
+            value: albedo,
+        });
+    }
+
+    let config = RefEtConfig {
+        albedo,
+        ..RefEtConfig::default()
+    };
+    let values = DailyValues::from_output(input, &config)?;
+    Ok(components_from_values(&values, &config))
+}
+
+/// Calculates the short and tall reference et from resolved daily values.
+pub(crate) fn ref_et_from_values(values: &DailyValues, config: &RefEtConfig) -> (f64, f64) {
+    let components = components_from_values(values, config);
+    (components.short, components.tall)
+}
+
+/// Calculates every term of the ASCE Standardized daily equation from resolved daily values.
+pub(crate) fn components_from_values(
+    values: &DailyValues,
+    config: &RefEtConfig,
+) -> RefEtComponents {
+    const LAMDA: f64 = 0.408;
+    let ea = values.ea;
+
+    let pressure = values.pressure();
+    let gamma = psy_constant(pressure);
+    let tmean = mean_temp(values.tmax, values.tmin);
+    let delta = es_slope(tmean);
+    let saturation_vapor_pressure = es(values.tmax, values.tmin);
+
+    let ra = calc_ra(values.latitude, values.doy);
+    let rso = calc_rso(ra, values.z);
+    let (rs, _) = resolve_rs(values, config, ra, rso);
+
+    let fcd = calc_fcd(rso, rs);
+    let rnl = calc_rnl_with_sigma(fcd, ea, values.tmax, values.tmin, config.sigma);
+    let rns = calc_rns_with_albedo(rs, config.albedo);
+    // a net radiometer reading replaces the estimate, Rns and Rnl are still reported from Rs
+    let rn = values.rn.unwrap_or_else(|| calc_rn(rns, rnl));
+    let u2 = calc_ws(values.ws, values.wz);
+    let u2_tall = if config.alfalfa_wind_profile {
+        calc_ws_to_height_over_crop(values.ws, values.wz, 2.0, ALFALFA_HEIGHT)
+    } else {
+        u2
+    };
+
+    let et = |cn: f64, cd: f64, u2: f64| {
+        (LAMDA * delta * (rn - values.g)
+            + gamma * (cn / (tmean + 273.0)) * u2 * (saturation_vapor_pressure - ea))
+            / (delta + gamma * (1.0 + cd * u2))
+    };
+
+    RefEtComponents {
+        pressure,
+        gamma,
+        tmean,
+        delta,
+        es: saturation_vapor_pressure,
+        ea,
+        vpd: saturation_vapor_pressure - ea,
+        ra,
+        rso,
+        rs,
+        fcd,
+        rns,
+        rnl,
+        rn,
+        u2,
+        short: et(config.cn_short, config.cd_short, u2),
+        tall: et(config.cn_tall, config.cd_tall, u2_tall),
+        wind_defaulted: false,
+    }
+}
+
+/// How the solar radiation used for the day was obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsDecision {
+    Measured,             // measured Rs used as supplied
+    HargreavesUsed,       // Hargreaves-Samani estimate used as computed
+    HargreavesClamped,    // Hargreaves-Samani estimate exceeded Rso and was limited to Rso
+    SunshineDerived,      // estimated from sunshine hours with the Angstrom formula
+    CloudCoverDerived,    // estimated from cloud cover as a fraction of Rso
+    MeasuredClampedToRso, // measured Rs exceeded Rso and was limited to Rso by the config
+}
+
+/// Quality-control flag for a measured solar radiation value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolarQcFlag {
+    Ok,              // Rs is within the physically expected range
+    ExceedsClearSky, // Rs is above clear sky radiation, a dirty or miscalibrated pyranometer
+    Suspicious,      // Rs is below 20% of clear sky radiation, possible shading or sensor fault
+}
+
+/// Screens a measured solar radiation value against clear sky radiation.
+///
+/// # Arguments
+///
+/// * `rs` - Measured solar radiation in MJ/m2/day.
+/// * `rso` - Clear sky solar radiation for the day in MJ/m2/day (Eq. 19).
+///
+/// # Returns
+///
+/// * `ExceedsClearSky` when Rs > Rso, `Suspicious` when Rs/Rso < 0.2, and otherwise `Ok`.
+pub fn qc_solar_radiation(rs: f64, rso: f64) -> SolarQcFlag {
+    if rs > rso {
+        SolarQcFlag::ExceedsClearSky
+    } else if rs < 0.2 * rso {
+        SolarQcFlag::Suspicious
+    } else {
+        SolarQcFlag::Ok
+    }
+}
+
+/// Chooses the solar radiation for the day, using measured Rs when present, then the Angstrom
+/// estimate from sunshine hours, and otherwise the Hargreaves-Samani estimate, limited to clear sky
+/// radiation unless the config disables it. Measured Rs is only limited to Rso when
+/// `clamp_measured_rs` is set.
+pub(crate) fn resolve_rs(
+    values: &DailyValues,
+    config: &RefEtConfig,
+    ra: f64,
+    rso: f64,
+) -> (f64, RsDecision) {
+    if let Some(rs_value) = values.rs {
+        if config.clamp_measured_rs && rs_value > rso {
+            return (rso, RsDecision::MeasuredClampedToRso);
+        }
+        return (rs_value, RsDecision::Measured);
+    }
+
+    if let Some(n) = values.sunshine {
+        let rs_value = calc_rs_from_sunshine(
+            n,
+            values.doy,
+            values.latitude,
+            config.angstrom_a,
+            config.angstrom_b,
+        );
+        return (rs_value, RsDecision::SunshineDerived);
+    }
+
+    if let Some(cloud) = values.cloud {
+        return (
+            calc_rs_from_cloud_cover(cloud, rso),
+            RsDecision::CloudCoverDerived,
+        );
+    }
+
+    let harg_rs =
+        calculate_hargreaves_samani_rs(values.tmax, values.tmin, ra, config.hargreaves_krs);
+    // limit rs to clear sky radiation
+    if config.clamp_hargreaves_rs && harg_rs > rso {
+        (rso, RsDecision::HargreavesClamped)
+    } else {
+        (harg_rs, RsDecision::HargreavesUsed)
+    }
 }
 
 /// Calculates the atmospheric pressure at a given altitude (z) in meters.
@@ -123,10 +650,8 @@ pub fn calculate_ref_et(input: &Output) -> (f64, f64) {
 /// # Returns
 ///
 /// The atmospheric pressure in Pascals.
-fn calc_atmospheric_pressure(z: f64) -> f64 {
-    let mut calc_1 = (293.0 - 0.0065 * z) / 293.0;
-    calc_1 = calc_1.powf(5.26);
-    calc_1 * 101.3
+pub(crate) fn calc_atmospheric_pressure(z: f64) -> f64 {
+    asce::pressure(z)
 }
 
 /// Calculates the psychrometric constant based on atmospheric pressure.
@@ -138,8 +663,8 @@ fn calc_atmospheric_pressure(z: f64) -> f64 {
 /// # Returns
 ///
 /// The psychrometric constant in Pascals.
-fn psy_constant(atmospheric_pressure: f64) -> f64 {
-    atmospheric_pressure * 0.000665
+pub(crate) fn psy_constant(atmospheric_pressure: f64) -> f64 {
+    asce::psy_constant(atmospheric_pressure)
 }
 
 /// Calculates the mean temperature from the given maximum and minimum temperatures.
@@ -152,7 +677,7 @@ fn psy_constant(atmospheric_pressure: f64) -> f64 {
 /// # Returns
 ///
 /// The mean temperature in degrees Celsius.
-fn mean_temp(max_temp: f64, min_temp: f64) -> f64 {
+pub(crate) fn mean_temp(max_temp: f64, min_temp: f64) -> f64 {
     (max_temp + min_temp) / 2.0
 }
 
@@ -165,12 +690,8 @@ fn mean_temp(max_temp: f64, min_temp: f64) -> f64 {
 /// # Returns
 ///
 /// The slope of the vapor pressure curve at the given mean temperature.
-fn es_slope(tmean: f64) -> f64 {
-    let e = (17.27 * tmean) / (tmean + 237.3);
-    let num = 2503.0 * e.exp();
-    let denom = (tmean + 237.3).powi(2);
-
-    num / denom
+pub(crate) fn es_slope(tmean: f64) -> f64 {
+    asce::es_slope(tmean)
 }
 
 /// Calculates the daily Saturation Vapor Pressure (Eq. 6)
@@ -187,8 +708,8 @@ fn es_slope(tmean: f64) -> f64 {
 /// # Panics
 ///
 /// This function will panic if the provided temperatures are not valid.
-fn es(max_temp: f64, min_temp: f64) -> f64 {
-    (eo(max_temp) + eo(min_temp)) / 2.0
+pub(crate) fn es(max_temp: f64, min_temp: f64) -> f64 {
+    asce::es(max_temp, min_temp)
 }
 
 // eo is a function to calculate the saturation vapor pressure at a given temperature (Eq. 7)
@@ -206,10 +727,81 @@ fn es(max_temp: f64, min_temp: f64) -> f64 {
 /// # Panics
 ///
 /// This function will panic if the provided temperature is not valid.
-fn eo(temp: f64) -> f64 {
-    0.6108 * E.powf((17.27 * temp) / (temp + 237.3))
+pub(crate) fn eo(temp: f64) -> f64 {
+    asce::eo(temp)
+}
+
+/// Calculates the daily Saturation Vapor Pressure (Eq. 6) for many stations or grid cells at once.
+///
+/// The loop is branch free over equal length slices with the bounds checks hoisted, and `exp` is
+/// replaced by a polynomial approximation made only of multiplies, adds, and a bit shift, so the
+/// compiler can vectorize the whole loop instead of calling `exp` once per value. The trade is
+/// accuracy: the result agrees with the scalar `es` to a relative error below 1e-14 for
+/// temperatures from -50 to 60 °C, far below the precision of the inputs.
+///
+/// # Arguments
+///
+/// * `tmax` - The maximum temperatures in degrees Celsius.
+/// * `tmin` - The minimum temperatures in degrees Celsius.
+/// * `out` - Receives the saturation vapor pressures in kPa.
+///
+/// # Panics
+///
+/// This function will panic if the three slices are not the same length.
+pub fn es_batch(tmax: &[f64], tmin: &[f64], out: &mut [f64]) {
+    assert_eq!(
+        tmax.len(),
+        out.len(),
+        "tmax and out must be the same length"
+    );
+    assert_eq!(
+        tmin.len(),
+        out.len(),
+        "tmin and out must be the same length"
+    );
+
+    for ((es_value, &max_temp), &min_temp) in out.iter_mut().zip(tmax).zip(tmin) {
+        let eo_max = 0.6108 * exp_poly((17.27 * max_temp) / (max_temp + 237.3));
+        let eo_min = 0.6108 * exp_poly((17.27 * min_temp) / (min_temp + 237.3));
+        *es_value = (eo_max + eo_min) / 2.0;
+    }
 }
 
+/// Taylor coefficients of exp, 1/12! down to 1/0!, in Horner order.
+const EXP_TAYLOR: [f64; 13] = [
+    2.08767569878681e-09,
+    2.505210838544172e-08,
+    2.755731922398589e-07,
+    2.7557319223985893e-06,
+    2.48015873015873e-05,
+    0.0001984126984126984,
+    0.001388888888888889,
+    0.008333333333333333,
+    0.041666666666666664,
+    0.16666666666666666,
+    0.5,
+    1.0,
+    1.0,
+];
+
+/// Branch free exp for `es_batch`.
+///
+/// Splits `x` into `k ln 2 + r` with `|r| <= ln 2 / 2`, evaluates exp(r) with a degree 12 Taylor
+/// polynomial, whose truncation error is below 2e-16 on that interval, and scales by 2^k by
+/// writing `k` into the exponent bits. Only valid for `|x| <= 700`, which covers the Tetens
+/// exponent at any temperature above about -200 °C.
+fn exp_poly(x: f64) -> f64 {
+    let k = (x * std::f64::consts::LOG2_E).round();
+    let r = x - k * std::f64::consts::LN_2;
+    let p = EXP_TAYLOR.iter().fold(0.0, |acc, &c| acc * r + c);
+    p * f64::from_bits(((k as i64 + 1023) as u64) << 52)
+}
+
+/// Length of the year used by Eqs. 23 and 24 in the ASCE Standardized equation.
+const DAYS_IN_YEAR: f64 = 365.0;
+/// Length of a leap year, used by `calc_ra_for_date`.
+const DAYS_IN_LEAP_YEAR: f64 = 366.0;
+
 /// Calculates the inverse relative distance factor of the Earth to the Sun. Found in equation 23.
 ///
 /// # Arguments
@@ -219,8 +811,12 @@ fn eo(temp: f64) -> f64 {
 /// # Returns
 ///
 /// * The inverse relative distance factor.
-fn inverse_rel_dist_factor(doy: u32) -> f64 {
-    1.0 + 0.033 * ((2.0 * PI / 365.0) * doy as f64).cos() // Eq. 23
+pub(crate) fn inverse_rel_dist_factor(doy: u32) -> f64 {
+    inverse_rel_dist_factor_in_year(doy, DAYS_IN_YEAR)
+}
+
+fn inverse_rel_dist_factor_in_year(doy: u32, days_in_year: f64) -> f64 {
+    asce::inverse_rel_dist_factor(doy, days_in_year)
 }
 
 /// Calculates the solar declination. Found in equation 24.
@@ -232,8 +828,12 @@ fn inverse_rel_dist_factor(doy: u32) -> f64 {
 /// # Returns
 ///
 /// * The solar declination.
-fn solar_declin(doy: u32) -> f64 {
-    0.409 * ((2.0 * PI / 365.0) * doy as f64 - 1.39).sin() // Eq. 24
+pub(crate) fn solar_declin(doy: u32) -> f64 {
+    solar_declin_in_year(doy, DAYS_IN_YEAR)
+}
+
+fn solar_declin_in_year(doy: u32, days_in_year: f64) -> f64 {
+    asce::solar_declin(doy, days_in_year)
 }
 
 /// Calculates the sunset hour angle. Found in equation 27.
@@ -245,9 +845,23 @@ fn solar_declin(doy: u32) -> f64 {
 ///
 /// # Returns
 ///
-/// * The sunset hour angle.
-fn sunset_hour_angle(lat: f64, delta: f64) -> f64 {
-    (-lat.tan() * delta.tan()).acos() // Eq. 27
+/// * The sunset hour angle, 0 during polar night and π during polar day.
+pub(crate) fn sunset_hour_angle(lat: f64, delta: f64) -> f64 {
+    asce::sunset_hour_angle(lat, delta)
+}
+
+/// Calculates the maximum possible duration of daylight, N = 24/π * ωs (FAO-56 Eq. 34).
+///
+/// # Arguments
+///
+/// * `latitude_rad` - Latitude in radians.
+/// * `doy` - Day of the year.
+///
+/// # Returns
+///
+/// * Daylight hours, 0 during polar night and 24 during polar day.
+pub fn daylight_hours(latitude_rad: f64, doy: u32) -> f64 {
+    24.0 / PI * sunset_hour_angle(latitude_rad, solar_declin(doy)) // FAO-56 Eq. 34
 }
 
 /// Calculates the Extraterrestrial Radiation for 24-Hour Periods. Found in equation 21.
@@ -260,18 +874,63 @@ fn sunset_hour_angle(lat: f64, delta: f64) -> f64 {
 /// # Returns
 ///
 /// * The Extraterrestrial Radiation for 24-Hour Periods.
-fn calc_ra(latitude: f64, doy: u32) -> f64 {
-    // println!("Latitude: {latitude}, DOY: {doy}");
-    let dr = inverse_rel_dist_factor(doy);
-    let delta = solar_declin(doy);
-    let omega = sunset_hour_angle(latitude, delta);
-    // println!("Dr: {dr}, delta: {delta}, omega: {omega}");
+pub(crate) fn calc_ra(latitude: f64, doy: u32) -> f64 {
+    calc_ra_in_year(latitude, doy, DAYS_IN_YEAR)
+}
+
+/// Calculates the Extraterrestrial Radiation for 24-Hour Periods (Eq. 21) for a calendar date,
+/// using 2π/366 in Eqs. 23 and 24 during leap years so day 366 doesn't run past the annual cycle.
+///
+/// # Arguments
+///
+/// * `latitude_rad` - Latitude in radians.
+/// * `date` - The day to calculate Ra for.
+///
+/// # Returns
+///
+/// * Ra in MJ/m2/day.
+pub fn calc_ra_for_date(latitude_rad: f64, date: &DateTime<Utc>) -> f64 {
+    let day = date.date_naive();
+    let days_in_year = if day.leap_year() {
+        DAYS_IN_LEAP_YEAR
+    } else {
+        DAYS_IN_YEAR
+    };
+
+    calc_ra_in_year(latitude_rad, day.ordinal(), days_in_year)
+}
 
-    24.0 / PI
-        * 4.92
-        * dr
-        * (omega * latitude.sin() * delta.sin() + latitude.cos() * delta.cos() * omega.sin())
-    // Eq. 21
+fn calc_ra_in_year(latitude: f64, doy: u32, days_in_year: f64) -> f64 {
+    asce::ra(latitude, doy, days_in_year)
+}
+
+/// Calculates the Extraterrestrial Radiation for 24-Hour Periods (Eq. 21) after checking that the
+/// latitude is in radians and the day of the year exists.
+///
+/// # Arguments
+///
+/// * `latitude_rad` - Latitude in radians, between -π/2 and π/2.
+/// * `doy` - Day of the year, 1 to 366.
+///
+/// # Returns
+///
+/// * Ra in MJ/m2/day, or `OutOfRange` for a latitude beyond ±π/2, such as one given in degrees,
+///   or a day of the year outside 1 to 366.
+pub fn calc_ra_checked(latitude_rad: f64, doy: u32) -> Result<f64, RefEtError> {
+    if !(-PI / 2.0..=PI / 2.0).contains(&latitude_rad) {
+        return Err(RefEtError::OutOfRange {
+            field: "latitude",
+            value: latitude_rad,
+        });
+    }
+    if !(1..=366).contains(&doy) {
+        return Err(RefEtError::OutOfRange {
+            field: "doy",
+            value: doy as f64,
+        });
+    }
+
+    Ok(calc_ra(latitude_rad, doy))
 }
 
 /// Calculates the clear-sky solar radiation. Found in equation 19.
@@ -285,8 +944,8 @@ fn calc_ra(latitude: f64, doy: u32) -> f64 {
 ///
 /// # Formula
 /// Uses the formula: RSO = (0.75 + 2e-5 * z) * ra
-fn calc_rso(ra: f64, z: f64) -> f64 {
-    (0.75 + 2e-5 * z) * ra
+pub(crate) fn calc_rso(ra: f64, z: f64) -> f64 {
+    asce::rso(ra, z)
 }
 
 /// Calculates the fraction of clear day (FCD).
@@ -300,12 +959,11 @@ fn calc_rso(ra: f64, z: f64) -> f64 {
 ///
 /// # Returns
 ///
-/// The fraction of clear day (FCD).
+/// The fraction of clear day (FCD). An Rs or Rso of 0, as on a polar night or with a failed
+/// sensor, is treated as fully overcast with Rs/Rso at its lower limit of 0.3, giving an FCD of
+/// 0.055 rather than dividing by zero.
 pub fn calc_fcd(rso: f64, rs: f64) -> f64 {
-    let mut relative_solar_radiation = rs / rso;
-
-    relative_solar_radiation = relative_solar_radiation.clamp(0.3, 1.0);
-    relative_solar_radiation * 1.35 - 0.35
+    asce::fcd(rso, rs)
 }
 
 /// calc_rnl is a function to compute net long-wave radiation  equation 17.
@@ -320,11 +978,77 @@ pub fn calc_fcd(rso: f64, rs: f64) -> f64 {
 /// # Returns
 ///
 /// * Net long-wave radiation
-fn calc_rnl(fcd: f64, ea: f64, tmax: f64, tmin: f64) -> f64 {
-    const SIGMA: f64 = 4.901e-9;
+pub fn calc_rnl(fcd: f64, ea: f64, tmax: f64, tmin: f64) -> f64 {
+    calc_rnl_with_model(fcd, ea, tmax, tmin, EmissivityModel::Fao)
+}
+
+/// Form of the net emissivity term of the net long-wave radiation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum EmissivityModel {
+    /// The ASCE and FAO-56 form 0.34 - 0.14 * sqrt(ea).
+    #[default]
+    Fao,
+    /// The Brunt form a - b * sqrt(ea) with calibrated coefficients.
+    Brunt { a: f64, b: f64 },
+    /// The Idso-Jackson form -0.02 + 0.261 * exp(-7.77e-4 * T^2) from the mean air temperature in
+    /// Celsius.
+    IdsoJackson,
+}
+
+impl EmissivityModel {
+    /// The net emissivity between the surface and the atmosphere.
+    ///
+    /// # Arguments
+    ///
+    /// * `ea` - Actual vapor pressure in kPa.
+    /// * `tmean` - Mean air temperature in Celsius, used by `IdsoJackson`.
+    pub fn net_emissivity(&self, ea: f64, tmean: f64) -> f64 {
+        match *self {
+            EmissivityModel::Fao => 0.34 - 0.14 * ea.sqrt(),
+            EmissivityModel::Brunt { a, b } => a - b * ea.sqrt(),
+            EmissivityModel::IdsoJackson => -0.02 + 0.261 * (-7.77e-4 * tmean.powi(2)).exp(),
+        }
+    }
+}
+
+/// Computes net long-wave radiation (equation 17) with the net emissivity of the given model, for
+/// calibrating the long-wave term to a region such as the humid tropics.
+///
+/// # Arguments
+///
+/// * `fcd` - Cloudiness factor
+/// * `ea` - Actual vapor pressure in kPa
+/// * `tmax` - Maximum temperature in Celsius
+/// * `tmin` - Minimum temperature in Celsius
+/// * `model` - The net emissivity form, `EmissivityModel::Fao` for the standard equation.
+///
+/// # Returns
+///
+/// * Net long-wave radiation in MJ/m2/day
+pub fn calc_rnl_with_model(fcd: f64, ea: f64, tmax: f64, tmin: f64, model: EmissivityModel) -> f64 {
+    rnl(
+        fcd,
+        model.net_emissivity(ea, mean_temp(tmax, tmin)),
+        tmax,
+        tmin,
+        asce::SIGMA,
+    )
+}
+
+/// Computes net long-wave radiation (equation 17) with a Stefan-Boltzmann constant given in
+/// MJ/K4/m2/day.
+pub(crate) fn calc_rnl_with_sigma(fcd: f64, ea: f64, tmax: f64, tmin: f64, sigma: f64) -> f64 {
+    rnl(
+        fcd,
+        EmissivityModel::Fao.net_emissivity(ea, mean_temp(tmax, tmin)),
+        tmax,
+        tmin,
+        sigma,
+    )
+}
 
-    SIGMA * fcd * (0.34 - 0.14 * ea.sqrt()) * ((tmax + 273.16).powi(4) + (tmin + 273.16).powi(4))
-        / 2.0
+fn rnl(fcd: f64, net_emissivity: f64, tmax: f64, tmin: f64, sigma: f64) -> f64 {
+    asce::rnl(fcd, net_emissivity, tmax, tmin, sigma)
 }
 
 /// Calculates the net solar or short-wave radiation. Found in equation 16.
@@ -336,9 +1060,14 @@ fn calc_rnl(fcd: f64, ea: f64, tmax: f64, tmin: f64) -> f64 {
 /// # Returns
 ///
 /// Net solar radiation after accounting for albedo.
-fn calc_rns(rs: f64) -> f64 {
+pub fn calc_rns(rs: f64) -> f64 {
     const ALPHA: f64 = 0.23;
-    (1.0 - ALPHA) * rs
+    calc_rns_with_albedo(rs, ALPHA)
+}
+
+/// Calculates the net short-wave radiation (equation 16) for a surface with the given albedo.
+pub fn calc_rns_with_albedo(rs: f64, albedo: f64) -> f64 {
+    (1.0 - albedo) * rs
 }
 
 /// Calculates the net radiation (Rn) based on the incoming shortwave radiation (Rns) and
@@ -352,7 +1081,7 @@ fn calc_rns(rs: f64) -> f64 {
 /// # Returns
 ///
 /// * `f64` - Net radiation (Rn)
-fn calc_rn(rns: f64, rnl: f64) -> f64 {
+pub(crate) fn calc_rn(rns: f64, rnl: f64) -> f64 {
     rns - rnl
 }
 
@@ -366,22 +1095,167 @@ fn calc_rn(rns: f64, rnl: f64) -> f64 {
 /// # Returns
 ///
 /// * Adjusted wind speed at 2m height.
-fn calc_ws(ws: f64, wz: f64) -> f64 {
-    if wz == 2.0 {
-        return ws;
-    }
-
-    ws * (4.87 / (67.8 * wz - 5.42).ln()) // Eq. 33
+pub(crate) fn calc_ws(ws: f64, wz: f64) -> f64 {
+    asce::u2(ws, wz)
 }
 
-fn calculate_hargreaves_samani_rs(tmax: f64, tmin: f64, ra: f64) -> f64 {
-    const ADJ_COEFFICIENT: f64 = 0.16;
-    ADJ_COEFFICIENT * ra * (tmax - tmin).sqrt()
+/// Calculates solar radiation from the duration of bright sunshine with the Angstrom formula
+/// (FAO-56 Eq. 35): Rs = (a_s + b_s * n / N) * Ra.
+///
+/// # Arguments
+///
+/// * `n` - Actual duration of sunshine in hours.
+/// * `doy` - Day of the year.
+/// * `latitude_rad` - Latitude in radians.
+/// * `a_s` - Fraction of Ra reaching the earth on overcast days, 0.25 when uncalibrated.
+/// * `b_s` - Additional fraction of Ra reaching the earth on clear days, 0.50 when uncalibrated.
+///
+/// # Returns
+///
+/// * Solar radiation in MJ/m2/day.
+pub fn calc_rs_from_sunshine(n: f64, doy: u32, latitude_rad: f64, a_s: f64, b_s: f64) -> f64 {
+    let daylight_hours = daylight_hours(latitude_rad, doy);
+    let relative_sunshine = if daylight_hours > 0.0 {
+        (n / daylight_hours).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    calc_rs_from_sunshine_fraction(relative_sunshine, doy, latitude_rad, a_s, b_s)
+}
+
+/// Calculates solar radiation with the Angstrom formula (FAO-56 Eq. 35) from sunshine published as
+/// a fraction of the possible, n/N, rather than hours.
+///
+/// # Arguments
+///
+/// * `fraction` - Relative sunshine duration n/N from 0 to 1. Values above 1 are read as a percent
+///   of possible sunshine and divided by 100, like relative humidity. The result is limited to 0
+///   to 1.
+/// * `doy` - Day of the year.
+/// * `latitude_rad` - Latitude in radians.
+/// * `a_s` - Fraction of Ra reaching the earth on overcast days, 0.25 when uncalibrated.
+/// * `b_s` - Additional fraction of Ra reaching the earth on clear days, 0.50 when uncalibrated.
+///
+/// # Returns
+///
+/// * Solar radiation in MJ/m2/day.
+pub fn calc_rs_from_sunshine_fraction(
+    fraction: f64,
+    doy: u32,
+    latitude_rad: f64,
+    a_s: f64,
+    b_s: f64,
+) -> f64 {
+    let fraction = if fraction > 1.0 {
+        fraction / 100.0
+    } else {
+        fraction
+    };
+
+    (a_s + b_s * fraction.clamp(0.0, 1.0)) * calc_ra(latitude_rad, doy)
+}
+
+/// Fraction of clear-sky radiation blocked by a fully overcast sky. Inverting the cloudiness
+/// function (Eq. 18) with fcd read as the clear fraction of the sky gives 1/1.35, about 0.74.
+const CLOUD_COVER_K: f64 = 1.0 / 1.35;
+
+/// Calculates solar radiation from cloud cover for forecasts and observations that don't carry
+/// Rs: Rs = Rso * (1 - k * cloud cover) with k = 1/1.35.
+///
+/// # Arguments
+///
+/// * `cloud_fraction` - Cloud cover from clear to overcast, as a fraction from 0 to 1, oktas above
+///   1 up to 8, or a percentage above 8 up to 100. A value of 1 is read as an overcast fraction,
+///   not 1 okta. The scaled fraction is limited to 0 to 1.
+/// * `rso` - Clear-sky radiation in MJ/m2/day.
+///
+/// # Returns
+///
+/// * Solar radiation in MJ/m2/day.
+pub fn calc_rs_from_cloud_cover(cloud_fraction: f64, rso: f64) -> f64 {
+    rso * (1.0 - CLOUD_COVER_K * scale_cloud_cover(cloud_fraction))
+}
+
+/// Scales cloud cover given as a fraction, oktas, or a percentage to a fraction from 0 to 1, the
+/// way `calc_rs_from_cloud_cover` reads it.
+fn scale_cloud_cover(cloud_cover: f64) -> f64 {
+    let fraction = if cloud_cover > 8.0 {
+        cloud_cover / 100.0
+    } else if cloud_cover > 1.0 {
+        cloud_cover / 8.0
+    } else {
+        cloud_cover
+    };
+
+    fraction.clamp(0.0, 1.0)
+}
+
+/// Checks that cloud cover is a fraction, oktas, or a percentage, 0 to 100 in any of them.
+pub(crate) fn check_cloud_cover(cloud_cover: f64) -> Result<(), RefEtError> {
+    if !(0.0..=100.0).contains(&cloud_cover) {
+        return Err(RefEtError::OutOfRange {
+            field: "cloud_cover",
+            value: cloud_cover,
+        });
+    }
+    Ok(())
+}
+
+/// Height in meters of the clipped grass reference surface.
+const GRASS_HEIGHT: f64 = 0.12;
+/// Height in meters of the alfalfa reference surface.
+const ALFALFA_HEIGHT: f64 = 0.5;
+
+/// Adjusts wind speed between measurement heights over the clipped grass reference surface.
+///
+/// This is the full logarithmic wind profile (ASCE Eq. B.14) that Eq. 33 simplifies. The two
+/// agree to within 0.001 m/s when adjusting to 2 m, and `calc_ws` keeps Eq. 33 so the daily
+/// calculation stays ASCE Standardized.
+///
+/// # Arguments
+///
+/// * `ws` - Wind speed at `from_z` in m/s.
+/// * `from_z` - Height in meters where the wind speed `ws` is measured.
+/// * `to_z` - Height in meters to adjust the wind speed to.
+///
+/// # Returns
+///
+/// * Wind speed at `to_z` in m/s.
+pub fn calc_ws_to_height(ws: f64, from_z: f64, to_z: f64) -> f64 {
+    calc_ws_to_height_over_crop(ws, from_z, to_z, GRASS_HEIGHT)
+}
+
+/// Adjusts wind speed between measurement heights over a surface of the given crop height, such
+/// as 0.5 m for the alfalfa reference.
+///
+/// The zero plane displacement is 0.67 h and the roughness length for momentum is 0.123 h.
+///
+/// # Arguments
+///
+/// * `ws` - Wind speed at `from_z` in m/s.
+/// * `from_z` - Height in meters where the wind speed `ws` is measured.
+/// * `to_z` - Height in meters to adjust the wind speed to.
+/// * `crop_height` - Height of the surface in meters.
+///
+/// # Returns
+///
+/// * Wind speed at `to_z` in m/s.
+pub fn calc_ws_to_height_over_crop(ws: f64, from_z: f64, to_z: f64, crop_height: f64) -> f64 {
+    let d = 0.67 * crop_height;
+    let zom = 0.123 * crop_height;
+
+    ws * ((to_z - d) / zom).ln() / ((from_z - d) / zom).ln()
+}
+
+fn calculate_hargreaves_samani_rs(tmax: f64, tmin: f64, ra: f64, krs: f64) -> f64 {
+    krs * ra * (tmax - tmin).sqrt()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn test_atmospheric_pressure_greeley() {
@@ -468,6 +1342,21 @@ mod tests {
         assert!((calculated_ws - 1.786).abs() < 0.001);
     }
 
+    #[test]
+    fn test_calculate_ws_to_height() {
+        // Given
+        let ws = 1.94;
+        let wz = 3.0;
+
+        // When
+        let grass = calc_ws_to_height(ws, wz, 2.0);
+        let alfalfa = calc_ws_to_height_over_crop(ws, wz, 2.0, 0.5);
+
+        assert!((grass - 1.786).abs() < 0.001);
+        assert!((grass - calc_ws(ws, wz)).abs() < 0.001);
+        assert!((alfalfa - 1.698).abs() < 0.001);
+    }
+
     #[test]
     fn test_inverse_rel_dist_factor() {
         // Given
@@ -505,6 +1394,39 @@ mod tests {
         assert!((sunset_hour_angle - 1.941).abs() < 0.001);
     }
 
+    #[test]
+    fn test_daylight_hours() {
+        // Given
+        let greeley = 40.41_f64.to_radians();
+
+        // When
+        let equator: Vec<f64> = [1, 80, 172, 355]
+            .map(|doy| daylight_hours(0.0, doy))
+            .to_vec();
+        let summer = daylight_hours(greeley, 172);
+        let winter = daylight_hours(greeley, 355);
+
+        assert!(equator.iter().all(|hours| (hours - 12.0).abs() < 1e-9));
+        assert!((summer - 14.89).abs() < 0.01);
+        assert!((winter - 9.11).abs() < 0.01);
+        assert_eq!(daylight_hours(70.0_f64.to_radians(), 172), 24.0);
+        assert_eq!(daylight_hours(70.0_f64.to_radians(), 355), 0.0);
+    }
+
+    #[test]
+    fn test_calculate_ra_polar() {
+        // Given
+        let latitude = 70.0_f64.to_radians();
+
+        // When
+        let summer = calc_ra(latitude, 172);
+        let winter = calc_ra(latitude, 355);
+
+        // the sun never sets at the summer solstice and never rises at the winter solstice
+        assert!(summer.is_finite() && summer > 40.0);
+        assert!(winter.abs() < 1e-9);
+    }
+
     #[test]
     fn test_calculate_ra() {
         // Given
@@ -518,6 +1440,23 @@ mod tests {
         assert!((ra - 41.626).abs() < 0.001);
     }
 
+    #[test]
+    fn test_calculate_ra_for_date_leap_year() {
+        // Given
+        let latitude = 40.41_f64.to_radians();
+        let leap = Utc.with_ymd_and_hms(2024, 12, 31, 0, 0, 0).unwrap();
+        let common = Utc.with_ymd_and_hms(2023, 12, 31, 0, 0, 0).unwrap();
+
+        // When
+        let leap_ra = calc_ra_for_date(latitude, &leap);
+        let common_ra = calc_ra_for_date(latitude, &common);
+
+        // Dec 31 closes the annual cycle in both years, while doy 366 over 365 days overshoots it
+        assert!((leap_ra - common_ra).abs() < 1e-9);
+        assert!((common_ra - calc_ra(latitude, 365)).abs() < 1e-12);
+        assert!((leap_ra - calc_ra(latitude, 366)).abs() > 1e-3);
+    }
+
     #[test]
     fn test_calculate_rso() {
         // Given
@@ -544,6 +1483,47 @@ mod tests {
         assert!((fcd - 0.5822).abs() < 0.001);
     }
 
+    #[test]
+    fn test_calculate_fcd_no_radiation() {
+        assert!((calc_fcd(32.44, 0.0) - 0.055).abs() < 1e-9);
+        assert!((calc_fcd(0.0, 0.0) - 0.055).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ref_et_zero_rs() {
+        // Given, a pyranometer reading nothing for the Greeley day
+        let output = greeley_output_with_rs(0.0);
+
+        // When
+        let RefEt { short, tall } = calculate_ref_et(&output).unwrap();
+        let measured_short = calculate_ref_et(&greeley_output()).unwrap().short;
+
+        assert!(short.is_finite() && tall.is_finite());
+        assert!(short < measured_short);
+    }
+
+    #[test]
+    fn test_ref_et_display() {
+        // Given
+        let et = RefEt::from((5.7134, 7.3189));
+
+        // When
+        let formatted = et.to_string();
+
+        assert_eq!(formatted, "ETos = 5.71 mm/day, ETrs = 7.32 mm/day");
+        assert_eq!(<(f64, f64)>::from(et), (5.7134, 7.3189));
+    }
+
+    #[test]
+    fn test_ref_et_negative_rs() {
+        let output = greeley_output_with_rs(-1.0);
+
+        assert!(matches!(
+            calculate_ref_et(&output),
+            Err(RefEtError::OutOfRange { field: "rs", .. })
+        ));
+    }
+
     #[test]
     fn test_calculate_rnl() {
         // Given
@@ -559,6 +1539,27 @@ mod tests {
         assert!((rnl - 3.96).abs() < 0.001);
     }
 
+    #[test]
+    fn test_calculate_rnl_with_model() {
+        // Given
+        let (fcd, ea, tmax, tmin) = (0.5822, 1.27, 32.4, 10.9);
+
+        // When
+        let fao = calc_rnl_with_model(fcd, ea, tmax, tmin, EmissivityModel::default());
+        let brunt = calc_rnl_with_model(
+            fcd,
+            ea,
+            tmax,
+            tmin,
+            EmissivityModel::Brunt { a: 0.34, b: 0.14 },
+        );
+        let idso = calc_rnl_with_model(fcd, ea, tmax, tmin, EmissivityModel::IdsoJackson);
+
+        assert!((fao - 3.96).abs() < 0.001);
+        assert!((brunt - fao).abs() < 1e-12);
+        assert!(idso > 0.0 && idso < fao);
+    }
+
     #[test]
     fn test_calculate_rns() {
         // Given
@@ -583,4 +1584,605 @@ mod tests {
         // greeley level based on the ASCE Standardized manual
         assert!((rn - 13.286).abs() < 0.001);
     }
+
+    #[test]
+    fn test_es_batch_matches_scalar() {
+        // Given
+        let tmax: Vec<f64> = (0..1000).map(|i| -40.0 + i as f64 * 0.1).collect();
+        let tmin: Vec<f64> = tmax.iter().map(|t| t - 10.0).collect();
+        let mut out = vec![0.0; tmax.len()];
+
+        // When
+        es_batch(&tmax, &tmin, &mut out);
+
+        for ((&batch, &max_temp), &min_temp) in out.iter().zip(&tmax).zip(&tmin) {
+            let scalar = es(max_temp, min_temp);
+            assert!((batch - scalar).abs() < 1e-9);
+            assert!((batch - scalar).abs() <= scalar * 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_exp_poly_matches_exp() {
+        for i in -1727..=1727 {
+            let x = i as f64 * 0.01;
+            assert!((exp_poly(x) - x.exp()).abs() <= x.exp() * 1e-14);
+        }
+    }
+
+    // run with `cargo test --release bench_es_batch -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_es_batch() {
+        use std::time::Instant;
+
+        let n = 1_000_000;
+        let tmax: Vec<f64> = (0..n).map(|i| 10.0 + (i % 300) as f64 * 0.1).collect();
+        let tmin: Vec<f64> = tmax.iter().map(|t| t - 12.0).collect();
+        let mut out = vec![0.0; n];
+
+        let start = Instant::now();
+        let scalar: Vec<f64> = tmax.iter().zip(&tmin).map(|(&a, &b)| es(a, b)).collect();
+        let scalar_time = start.elapsed();
+
+        let start = Instant::now();
+        es_batch(&tmax, &tmin, &mut out);
+        let batch_time = start.elapsed();
+
+        println!("scalar es: {:?}, es_batch: {:?}", scalar_time, batch_time);
+        assert_eq!(scalar.len(), out.len());
+    }
+
+    fn greeley_output() -> Output {
+        greeley_output_with_rs(22.4)
+    }
+
+    fn greeley_output_with_rs(rs: f64) -> Output {
+        Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(rs),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        )
+    }
+
+    fn greeley_values(tmax: f64, tmin: f64, rs: Option<f64>) -> DailyValues {
+        DailyValues {
+            tmax,
+            tmin,
+            ea: 1.27,
+            rs,
+            rn: None,
+            sunshine: None,
+            cloud: None,
+            ws: 1.94,
+            wz: 3.0,
+            z: 1462.4,
+            pressure: None,
+            latitude: 40.41_f64.to_radians(),
+            doy: 183,
+            g: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_tmin_fallback_ea_used_in_aerodynamic_term() {
+        // Given, the Greeley day without humidity and the same day with the Tmin dewpoint ea
+        let config = RefEtConfig::default();
+        let without_humidity = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            None,
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+        let ea = EaInput::new_tmin_with_offset(10.9, "C", config.tmin_dewpoint_offset)
+            .unwrap()
+            .ea()
+            .unwrap();
+        let with_ea = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(ea),
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When
+        let fallback = calculate_ref_et_with_config(&without_humidity, &config).unwrap();
+        let direct = calculate_ref_et_with_config(&with_ea, &config).unwrap();
+
+        // both the vapor pressure deficit and Rnl use the computed ea, not output.get_ea()
+        assert_eq!(fallback, direct);
+    }
+
+    #[test]
+    fn test_components_measured_pressure() {
+        // Given, the Greeley barometric pressure
+        let estimated = greeley_values(32.4, 10.9, Some(22.4));
+        let measured = DailyValues {
+            pressure: Some(85.17),
+            ..estimated
+        };
+
+        // When
+        let estimated = components_from_values(&estimated, &RefEtConfig::default());
+        let measured = components_from_values(&measured, &RefEtConfig::default());
+
+        assert_eq!(measured.pressure, 85.17);
+        assert!((measured.gamma - estimated.gamma).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_resolve_rs_measured() {
+        // Given
+        let values = greeley_values(32.4, 10.9, Some(22.4));
+
+        // When
+        let (rs, decision) = resolve_rs(&values, &RefEtConfig::default(), 41.63, 32.44);
+
+        assert_eq!(decision, RsDecision::Measured);
+        assert_eq!(rs, 22.4);
+    }
+
+    #[test]
+    fn test_resolve_rs_measured_above_rso() {
+        // Given
+        let values = greeley_values(32.4, 10.9, Some(35.0));
+        let clamp_config = RefEtConfig {
+            clamp_measured_rs: true,
+            ..RefEtConfig::default()
+        };
+
+        // When
+        let (default_rs, default_decision) =
+            resolve_rs(&values, &RefEtConfig::default(), 41.63, 32.44);
+        let (clamped_rs, clamped_decision) = resolve_rs(&values, &clamp_config, 41.63, 32.44);
+
+        assert_eq!(default_decision, RsDecision::Measured);
+        assert_eq!(default_rs, 35.0);
+        assert_eq!(clamped_decision, RsDecision::MeasuredClampedToRso);
+        assert_eq!(clamped_rs, 32.44);
+    }
+
+    #[test]
+    fn test_rs_from_sunshine_rio() {
+        // Given, FAO-56 Example 10 (Rio de Janeiro in May, 7.1 hours of sunshine)
+        let latitude = (-22.9_f64).to_radians();
+
+        // When
+        let rs = calc_rs_from_sunshine(7.1, 135, latitude, 0.25, 0.50);
+
+        // FAO-56 gives 14.5 MJ/m2/day
+        assert!((rs - 14.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_rs_from_sunshine_fraction() {
+        // Given
+        let latitude = 40.41_f64.to_radians();
+        let ra = calc_ra(latitude, 183);
+
+        // When
+        let clear = calc_rs_from_sunshine_fraction(1.0, 183, latitude, 0.25, 0.50);
+        let overcast = calc_rs_from_sunshine_fraction(0.0, 183, latitude, 0.25, 0.50);
+        let percent = calc_rs_from_sunshine_fraction(60.0, 183, latitude, 0.25, 0.50);
+
+        assert!((clear - 0.75 * ra).abs() < 1e-9);
+        assert!((overcast - 0.25 * ra).abs() < 1e-9);
+        let fraction = calc_rs_from_sunshine_fraction(0.6, 183, latitude, 0.25, 0.50);
+        assert!((percent - fraction).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rs_from_cloud_cover() {
+        // Given
+        let rso = 32.44;
+
+        // When
+        let clear = calc_rs_from_cloud_cover(0.0, rso);
+        let overcast = calc_rs_from_cloud_cover(1.0, rso);
+
+        assert_eq!(clear, rso);
+        // an overcast sky leaves 0.35/1.35 of Rso, the Rs/Rso at which fcd reaches 0
+        assert!((overcast - 0.35 / 1.35 * rso).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rs_from_cloud_cover_oktas_and_percent() {
+        // Given
+        let rso = 32.44;
+        let half = calc_rs_from_cloud_cover(0.5, rso);
+        let overcast = calc_rs_from_cloud_cover(1.0, rso);
+
+        // When / Then
+        assert_eq!(calc_rs_from_cloud_cover(4.0, rso), half);
+        assert_eq!(calc_rs_from_cloud_cover(50.0, rso), half);
+        assert_eq!(calc_rs_from_cloud_cover(8.0, rso), overcast);
+        assert_eq!(calc_rs_from_cloud_cover(100.0, rso), overcast);
+        assert!(check_cloud_cover(100.0).is_ok());
+        assert!(check_cloud_cover(150.0).is_err());
+        assert!(check_cloud_cover(-0.1).is_err());
+    }
+
+    #[test]
+    fn test_resolve_rs_cloud_cover_before_hargreaves() {
+        // Given
+        let values = DailyValues {
+            cloud: Some(0.5),
+            ..greeley_values(32.4, 10.9, None)
+        };
+
+        // When
+        let (rs, decision) = resolve_rs(&values, &RefEtConfig::default(), 41.63, 32.44);
+
+        assert_eq!(decision, RsDecision::CloudCoverDerived);
+        assert!((rs - 32.44 * (1.0 - 0.5 / 1.35)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resolve_rs_sunshine_before_hargreaves() {
+        // Given
+        let values = DailyValues {
+            sunshine: Some(12.0),
+            ..greeley_values(32.4, 10.9, None)
+        };
+
+        // When
+        let (_, decision) = resolve_rs(&values, &RefEtConfig::default(), 41.63, 32.44);
+
+        assert_eq!(decision, RsDecision::SunshineDerived);
+    }
+
+    #[test]
+    fn test_resolve_rs_hargreaves_used() {
+        // Given
+        let values = greeley_values(32.4, 10.9, None);
+
+        // When
+        let (rs, decision) = resolve_rs(&values, &RefEtConfig::default(), 41.63, 32.44);
+
+        assert_eq!(decision, RsDecision::HargreavesUsed);
+        assert!((rs - 30.88).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_resolve_rs_hargreaves_clamped() {
+        // Given, a 40 degree range puts the Hargreaves estimate above Rso
+        let values = greeley_values(40.0, 0.0, None);
+
+        // When
+        let (rs, decision) = resolve_rs(&values, &RefEtConfig::default(), 41.63, 32.44);
+
+        assert_eq!(decision, RsDecision::HargreavesClamped);
+        assert_eq!(rs, 32.44);
+    }
+
+    #[test]
+    fn test_components_display_units() {
+        // Given
+        let components = components_from_values(
+            &greeley_values(32.4, 10.9, Some(22.4)),
+            &RefEtConfig::default(),
+        );
+
+        // When
+        let text = components.to_string();
+
+        assert!(text.contains("Rn: "));
+        assert!(text
+            .lines()
+            .any(|line| line.starts_with("Rn: ") && line.ends_with("MJ/m²/day")));
+        assert!(RefEtComponents::units().contains(&("delta", "kPa/°C")));
+        assert_eq!(RefEtComponents::units().len(), 18);
+    }
+
+    #[test]
+    fn test_components_to_flat() {
+        // Given
+        let components = calculate_ref_et_detailed(&greeley_output()).unwrap();
+
+        // When
+        let flat = components.to_flat();
+
+        assert_eq!(flat.len(), 18);
+        assert_eq!(flat["net_radiation"], components.rn);
+        assert_eq!(flat["reference_et_short"], components.short);
+        assert_eq!(flat["wind_defaulted"], 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_components_json() {
+        // Given
+        let components = calculate_ref_et_detailed(&greeley_output()).unwrap();
+
+        // When
+        let flat_json = serde_json::to_string(&components.to_flat()).unwrap();
+        let json = serde_json::to_string(&components).unwrap();
+
+        assert!(flat_json.contains("\"net_radiation\""));
+        assert!(flat_json.contains("\"reference_et_short\""));
+        let parsed: RefEtComponents = serde_json::from_str(&json).unwrap();
+        assert!((parsed.rn - components.rn).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_with_defaults_windless_output() {
+        // Given
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            None,
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When
+        let components = calculate_ref_et_with_defaults(&output, 2.0).unwrap();
+
+        assert!(components.wind_defaulted);
+        assert_eq!(components.u2, 2.0);
+        assert!(components.short.is_finite() && components.short > 0.0);
+        assert!(components.tall > components.short);
+        assert_eq!(
+            calculate_ref_et(&output),
+            Err(RefEtError::MissingField("ws"))
+        );
+    }
+
+    #[test]
+    fn test_with_defaults_measured_wind() {
+        let output = greeley_output();
+
+        let components = calculate_ref_et_with_defaults(&output, 2.0).unwrap();
+
+        assert!(!components.wind_defaulted);
+        assert_eq!(
+            RefEt {
+                short: components.short,
+                tall: components.tall
+            },
+            calculate_ref_et(&output).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_vapor_pressure_deficit_greeley() {
+        // Given
+        let output = greeley_output();
+
+        // When
+        let vpd = vapor_pressure_deficit(&output).unwrap();
+
+        // es = 3.08 and ea = 1.27
+        assert!((vpd - 1.81).abs() < 0.01);
+        assert_eq!(vpd, calculate_ref_et_detailed(&output).unwrap().vpd);
+    }
+
+    #[test]
+    fn test_single_ref_et_matches_tuple() {
+        // Given
+        let output = greeley_output();
+
+        // When
+        let short = calculate_single_ref_et(&output, ReferenceCrop::ShortGrass).unwrap();
+        let tall = calculate_single_ref_et(&output, ReferenceCrop::TallAlfalfa).unwrap();
+
+        assert_eq!(RefEt { short, tall }, calculate_ref_et(&output).unwrap());
+    }
+
+    #[test]
+    fn test_et_units_conversions() {
+        assert_eq!(EtUnits::MmPerDay.from_mm_per_day(6.35), 6.35);
+        assert!((EtUnits::InchPerDay.from_mm_per_day(6.35) - 0.25).abs() < 1e-9);
+        assert!((EtUnits::MmPerHour.from_mm_per_day(6.0) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calculate_ref_et_in_inches() {
+        // Given
+        let output = greeley_output();
+
+        // When
+        let (short, tall) = calculate_ref_et_in(&output, EtUnits::InchPerDay).unwrap();
+
+        let RefEt {
+            short: short_mm,
+            tall: tall_mm,
+        } = calculate_ref_et(&output).unwrap();
+        assert!((short - short_mm / 25.4).abs() < 1e-9);
+        assert!((tall - tall_mm / 25.4).abs() < 1e-9);
+        assert!((short - 0.224).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_calc_ra_checked() {
+        // Given, the Greeley latitude passed in degrees by mistake
+        let result = calc_ra_checked(40.41, 183);
+
+        assert!(matches!(
+            result,
+            Err(RefEtError::OutOfRange {
+                field: "latitude",
+                ..
+            })
+        ));
+        assert!(calc_ra_checked(40.41_f64.to_radians(), 0).is_err());
+        assert!(calc_ra_checked(40.41_f64.to_radians(), 367).is_err());
+        assert_eq!(
+            calc_ra_checked(-33.87_f64.to_radians(), 183),
+            Ok(calc_ra(-33.87_f64.to_radians(), 183))
+        );
+    }
+
+    #[test]
+    fn test_qc_solar_radiation() {
+        assert_eq!(qc_solar_radiation(35.0, 32.0), SolarQcFlag::ExceedsClearSky);
+        assert_eq!(qc_solar_radiation(22.4, 32.44), SolarQcFlag::Ok);
+        assert_eq!(qc_solar_radiation(5.0, 32.44), SolarQcFlag::Suspicious);
+    }
+
+    #[test]
+    fn test_to_celsius() {
+        assert!((to_celsius(300.0, "K").unwrap() - 26.85).abs() < 1e-9);
+        assert!((to_celsius(273.15, "kelvin").unwrap()).abs() < 1e-9);
+        assert_eq!(to_celsius(21.5, "c"), Ok(21.5));
+        assert_eq!(to_celsius(212.0, "f"), Ok(100.0));
+        assert!(to_celsius(21.5, "kpa").is_err());
+    }
+
+    #[test]
+    fn test_calc_rns_with_albedo_water() {
+        // Given
+        let rs = 22.4;
+
+        // When
+        let rns = calc_rns_with_albedo(rs, 0.08);
+
+        assert!((rns - 0.92 * rs).abs() < 1e-9);
+        assert_eq!(calc_rns_with_albedo(rs, 0.23), calc_rns(rs));
+    }
+
+    #[test]
+    fn test_detailed_with_albedo() {
+        let output = greeley_output();
+
+        let water = calculate_ref_et_detailed_with_albedo(&output, 0.08).unwrap();
+
+        assert!((water.rns - 0.92 * 22.4).abs() < 1e-9);
+        assert!(calculate_ref_et_detailed_with_albedo(&output, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_default_coeffs_match_greeley() {
+        // Given
+        let output = greeley_output();
+
+        // When
+        let (short, tall) =
+            calculate_ref_et_with_coeffs(&output, &EtCoefficients::default()).unwrap();
+
+        assert_eq!(RefEt { short, tall }, calculate_ref_et(&output).unwrap());
+        assert!((short - 5.69).abs() < 0.01);
+        assert!((tall - 7.32).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_calibrated_coeffs_change_result() {
+        let output = greeley_output();
+        let coeffs = EtCoefficients {
+            cn_short: 1000.0,
+            ..EtCoefficients::default()
+        };
+
+        let (short, tall) = calculate_ref_et_with_coeffs(&output, &coeffs).unwrap();
+
+        assert!(short > calculate_ref_et(&output).unwrap().short);
+        assert_eq!(tall, calculate_ref_et(&output).unwrap().tall);
+    }
+
+    #[test]
+    fn test_alfalfa_wind_profile_changes_tall_only() {
+        // Given, wind measured at 3 m
+        let output = greeley_output();
+        let coeffs = EtCoefficients {
+            alfalfa_wind_profile: true,
+            ..EtCoefficients::default()
+        };
+
+        // When
+        let (short, tall) = calculate_ref_et_with_coeffs(&output, &coeffs).unwrap();
+        let RefEt {
+            short: standard_short,
+            tall: standard_tall,
+        } = calculate_ref_et(&output).unwrap();
+
+        assert_eq!(short, standard_short);
+        // the rougher alfalfa surface slows the wind at 2 m, lowering the aerodynamic term
+        assert!(tall < standard_tall);
+        assert!((tall - standard_tall).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_components_greeley() {
+        // Given
+        let values = greeley_values(32.4, 10.9, Some(22.4));
+
+        // When
+        let components = components_from_values(&values, &RefEtConfig::default());
+
+        assert!((components.ra - 41.63).abs() < 0.01);
+        assert!((components.rso - 32.44).abs() < 0.01);
+        assert!((components.rnl - 3.96).abs() < 0.01);
+        assert!((components.rn - 13.29).abs() < 0.01);
+        assert!((components.u2 - 1.787).abs() < 0.001);
+        assert_eq!(
+            (components.short, components.tall),
+            ref_et_from_values(&values, &RefEtConfig::default())
+        );
+    }
+
+    #[test]
+    fn test_calculate_ref_et_detailed_with_g() {
+        // Given
+        let output = greeley_output();
+
+        // When
+        let assumed = calculate_ref_et_detailed(&output).unwrap();
+        let zero = calculate_ref_et_detailed_with_g(&output, Some(0.0)).unwrap();
+        let measured = calculate_ref_et_detailed_with_g(&output, Some(1.0)).unwrap();
+
+        assert_eq!(zero, assumed);
+        // one MJ/m2/day less available energy removes 0.408 Δ / (Δ + γ(1 + Cd u2)) mm/day
+        let removed =
+            0.408 * assumed.delta / (assumed.delta + assumed.gamma * (1.0 + 0.34 * assumed.u2));
+        assert!((assumed.short - measured.short - removed).abs() < 1e-9);
+        assert!(measured.tall < assumed.tall);
+    }
+
+    #[test]
+    fn test_components_measured_rn() {
+        // Given
+        let values = DailyValues {
+            rn: Some(10.0),
+            ..greeley_values(32.4, 10.9, Some(22.4))
+        };
+
+        // When
+        let components = components_from_values(&values, &RefEtConfig::default());
+
+        assert_eq!(components.rn, 10.0);
+        assert!((components.fcd - 0.582).abs() < 0.001);
+        assert!(components.short < 5.69);
+    }
 }
@@ -1,8 +1,14 @@
+use crate::config::RefEtConfig;
+use crate::conversions::{dewpoint_from_rh, pa_to_kpa};
+use crate::et::to_celsius;
+use crate::RefEtError;
 use climate::output::Output;
 use climate::units::Units;
-use std::error::Error;
 use std::f64::consts::E;
+use std::fmt;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
 pub enum Method {
     Direct,
     DewPoint,
@@ -10,6 +16,9 @@ pub enum Method {
     DailyMaxRelativeHumidity,
     DailyMinRelativeHumidity,
     DailyMinAirTemperature,
+    MeanRelativeHumidity,
+    WetDryBulb,
+    MeanAirTemperature,
 }
 
 // EA (mean actual vapor pressure) has several calculation methods in ASCE Standarized, we support many but not all
@@ -20,15 +29,33 @@ pub enum Method {
 // DailyMaxRelativeHumidity - daily maximum relative humidity (put in Value, add Tmin)
 // DailyMinRelativeHumidity - daily minimum relative humidity (put in Value, add Tmax)
 // DailyMinAirTemperature - daily minimum air temperature (put in Value, add Tmin)
+// MeanRelativeHumidity - daily mean relative humidity with daily mean temperature (FAO-56 Eq. 19), ranked below
+//   the RHmax/RHmin methods and above the DailyMinAirTemperature fallback
+// WetDryBulb - wet and dry bulb temperatures from a psychrometer with its coefficient apsy (Eq. 14 and 15)
+// MeanAirTemperature - daily mean air temperature only, Tdew = Tmean - offset, the least accurate fallback for
+//   monthly datasets without humidity or Tmin
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
 pub struct EaInput {
-    input: Option<f64>, // Ea in kPa or Dewpoint in Celsius otherwise None
-    method: Method,     // method to calculate Ea from Method enum
-    rhmax: Option<f64>, // daily maximum relative humidity in %
-    rhmin: Option<f64>, // daily minimum relative humidity in %
-    tmax: Option<f64>,  // daily maximum air temperature in Celsius
-    tmin: Option<f64>,  // daily minimum air temperature in Celsius
+    input: Option<f64>,    // Ea in kPa or Dewpoint in Celsius otherwise None
+    method: Method,        // method to calculate Ea from Method enum
+    rhmax: Option<f64>,    // daily maximum relative humidity in %
+    rhmin: Option<f64>,    // daily minimum relative humidity in %
+    tmax: Option<f64>,     // daily maximum air temperature in Celsius
+    tmin: Option<f64>,     // daily minimum air temperature in Celsius
+    rhmean: Option<f64>,   // daily mean relative humidity in %
+    tmean: Option<f64>,    // daily mean air temperature in Celsius
+    twet: Option<f64>,     // wet bulb temperature in Celsius
+    tdry: Option<f64>,     // dry bulb temperature in Celsius
+    pressure: Option<f64>, // atmospheric pressure in kPa
+    apsy: Option<f64>,     // psychrometer coefficient in 1/C
+    tmin_offset: f64,      // degrees subtracted from tmin to estimate dewpoint in Celsius
+    tmean_offset: f64,     // degrees subtracted from tmean to estimate dewpoint in Celsius
 }
 
+/// Degrees Celsius subtracted from the daily mean temperature to estimate the dewpoint.
+const TMEAN_DEWPOINT_OFFSET: f64 = 2.0;
+
 impl EaInput {
     pub fn new_empty(method: Method) -> EaInput {
         EaInput {
@@ -38,85 +65,170 @@ impl EaInput {
             rhmin: None,
             tmax: None,
             tmin: None,
+            rhmean: None,
+            tmean: None,
+            twet: None,
+            tdry: None,
+            pressure: None,
+            apsy: None,
+            tmin_offset: RefEtConfig::default().tmin_dewpoint_offset,
+            tmean_offset: TMEAN_DEWPOINT_OFFSET,
         }
     }
 
-    pub fn new_from_output(output: &Output) -> EaInput {
+    /// Starts an `EaInputBuilder`, for choosing the method from whichever values are available
+    /// and reporting bad units and missing values as errors when it is built.
+    pub fn builder() -> EaInputBuilder {
+        EaInputBuilder::default()
+    }
+
+    pub fn new_from_output(output: &Output) -> Result<EaInput, RefEtError> {
+        EaInput::new_from_output_with_config(output, &RefEtConfig::default())
+    }
+
+    /// Creates an EaInput from the output like `new_from_output`, reading the output temperatures
+    /// in the configured units and applying the configured Tmin dewpoint offset when the Tmin
+    /// fallback is selected. An output carries no mean relative humidity, so records with only
+    /// mean RH go through `EaInput::builder().rh_mean`.
+    pub fn new_from_output_with_config(
+        output: &Output,
+        config: &RefEtConfig,
+    ) -> Result<EaInput, RefEtError> {
+        let temp_units = config.temperature_units.as_str();
+
         // first option is Use Ea set from output
         if output.get_ea().is_some() {
             EaInput::new_direct(output.get_ea().unwrap(), "kPa")
         } else if output.get_dewpoint().is_some() {
-            EaInput::new_dewpoint(output.get_dewpoint().unwrap(), "C")
+            EaInput::new_dewpoint(output.get_dewpoint().unwrap(), temp_units)
         } else if output.get_rhmin().is_some() && output.get_rhmax().is_some() {
             EaInput::new_rhmax_min(
                 output.get_rhmax().unwrap(),
                 output.get_rhmin().unwrap(),
-                "C",
+                "%",
                 output.get_tmax(),
                 output.get_tmin(),
-                "C",
+                temp_units,
             )
         } else if output.get_rhmax().is_some() {
-            EaInput::new_rhmax(output.get_rhmax().unwrap(), "C", output.get_tmax(), "C")
+            EaInput::new_rhmax(
+                output.get_rhmax().unwrap(),
+                "%",
+                output.get_tmax(),
+                temp_units,
+            )
         } else if output.get_rhmin().is_some() {
-            EaInput::new_rhmin(output.get_rhmin().unwrap(), "C", output.get_tmin(), "C")
+            EaInput::new_rhmin(
+                output.get_rhmin().unwrap(),
+                "%",
+                output.get_tmin(),
+                temp_units,
+            )
         } else {
-            EaInput::new_tmin(output.get_tmin(), "C")
+            EaInput::new_tmin_with_offset(
+                output.get_tmin(),
+                temp_units,
+                config.tmin_dewpoint_offset,
+            )
         }
     }
 
-    pub fn new_direct(input: f64, units: &str) -> EaInput {
-        let mut direct_value = 0.0;
-        if let Ok(unit) = Units::from_abbreviation(units) {
-            match unit {
-                Units::KiloPascals => direct_value = input,
-                Units::Pascals => {
-                    direct_value = Units::Pascals
-                        .convert(input, &Units::KiloPascals)
-                        .expect("Units conversion failed")
-                }
-                _ => panic!("Invalid units for EA Direct: {}", units),
-            }
-        } else {
-            panic!("Invalid units: {}", units)
-        };
+    /// Creates an EaInput from the output with the `preferred` method instead of the priority order
+    /// of `new_from_output`, so a dataset can be reproduced with the method it was published with.
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `MissingField` naming the first value the method needs that the output
+    ///   doesn't have. There is no fall through to another method. `MeanAirTemperature` uses the
+    ///   mean of Tmax and Tmin, while `MeanRelativeHumidity` and `WetDryBulb` need values an output
+    ///   doesn't carry and return `DomainError`; use `new_rh_mean` or `new_wet_dry_bulb` for them.
+    pub fn new_from_output_with_method(
+        output: &Output,
+        preferred: Method,
+    ) -> Result<EaInput, RefEtError> {
+        EaInput::new_from_output_with_method_with_config(output, preferred, &RefEtConfig::default())
+    }
 
-        EaInput {
-            input: Some(direct_value),
-            method: Method::Direct,
-            rhmax: None,
-            rhmin: None,
-            tmax: None,
-            tmin: None,
+    /// Creates an EaInput from the output with the `preferred` method like
+    /// `new_from_output_with_method`, reading the output temperatures in the configured units and
+    /// using the configured dewpoint offset for the Tmin method.
+    pub fn new_from_output_with_method_with_config(
+        output: &Output,
+        preferred: Method,
+        config: &RefEtConfig,
+    ) -> Result<EaInput, RefEtError> {
+        let temp_units = config.temperature_units.as_str();
+        let rhmax = || output.get_rhmax().ok_or(RefEtError::MissingField("rhmax"));
+        let rhmin = || output.get_rhmin().ok_or(RefEtError::MissingField("rhmin"));
+
+        match preferred {
+            Method::Direct => {
+                let ea = output.get_ea().ok_or(RefEtError::MissingField("ea"))?;
+                EaInput::new_direct(ea, "kPa")
+            }
+            Method::DewPoint => {
+                let dewpoint = output
+                    .get_dewpoint()
+                    .ok_or(RefEtError::MissingField("dewpoint"))?;
+                EaInput::new_dewpoint(dewpoint, temp_units)
+            }
+            Method::MaxMinRelativeHumidity => EaInput::new_rhmax_min(
+                rhmax()?,
+                rhmin()?,
+                "%",
+                output.get_tmax(),
+                output.get_tmin(),
+                temp_units,
+            ),
+            Method::DailyMaxRelativeHumidity => {
+                EaInput::new_rhmax(rhmax()?, "%", output.get_tmax(), temp_units)
+            }
+            Method::DailyMinRelativeHumidity => {
+                EaInput::new_rhmin(rhmin()?, "%", output.get_tmin(), temp_units)
+            }
+            Method::DailyMinAirTemperature => EaInput::new_tmin_with_offset(
+                output.get_tmin(),
+                temp_units,
+                config.tmin_dewpoint_offset,
+            ),
+            Method::MeanAirTemperature => {
+                EaInput::new_tmean((output.get_tmax() + output.get_tmin()) / 2.0, temp_units)
+            }
+            Method::MeanRelativeHumidity => Err(RefEtError::DomainError(
+                "an output carries no mean relative humidity",
+            )),
+            Method::WetDryBulb => Err(RefEtError::DomainError(
+                "an output carries no wet and dry bulb temperatures",
+            )),
         }
     }
 
-    pub fn new_dewpoint(tdew: f64, units: &str) -> EaInput {
-        let mut direct_value = 0.0;
-        if let Ok(unit) = Units::from_abbreviation(units) {
-            match unit {
-                Units::Celsius => direct_value = tdew,
-                Units::Fahrenheit => {
-                    direct_value = Units::Fahrenheit
-                        .convert(tdew, &Units::Celsius)
-                        .expect("Units conversion failed")
-                }
-                _ => panic!("Invalid units for dewpoint: {}", units),
-            }
-        } else {
-            panic!("Invalid units: {}", units)
-        };
+    /// Creates an EaInput from ea measured in "kPa" or "Pa".
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `InvalidUnits` when the units are not a pressure.
+    pub fn new_direct(input: f64, units: &str) -> Result<EaInput, RefEtError> {
+        EaInput::builder().direct(input, units).build()
+    }
 
-        EaInput {
-            input: Some(direct_value),
-            method: Method::DewPoint,
-            rhmax: None,
-            rhmin: None,
-            tmax: None,
-            tmin: None,
-        }
+    /// Creates an EaInput from the dewpoint temperature.
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `InvalidUnits` when the units are not a temperature.
+    pub fn new_dewpoint(tdew: f64, units: &str) -> Result<EaInput, RefEtError> {
+        EaInput::builder().dewpoint(tdew, units).build()
     }
 
+    /// Creates an EaInput from daily maximum and minimum relative humidity with Tmax and Tmin
+    /// (Eq. 11).
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `OutOfRange` when either humidity is outside 0 to 100 or `rhmax` is below
+    ///   `rhmin`, and `InvalidUnits` when `rh_units` is not "%", "percent", or "fraction" or the
+    ///   temperature units are not recognized.
     pub fn new_rhmax_min(
         rhmax: f64,
         rhmin: f64,
@@ -124,110 +236,205 @@ impl EaInput {
         tmax: f64,
         tmin: f64,
         temp_units: &str,
-    ) -> EaInput {
+    ) -> Result<EaInput, RefEtError> {
+        check_rh_units(rh_units)?;
+        check_rh("rhmax", rhmax)?;
+        check_rh("rhmin", rhmin)?;
+        if rhmax < rhmin {
+            return Err(RefEtError::OutOfRange {
+                field: "rhmax",
+                value: rhmax,
+            });
+        }
+
         let mut ea_input = EaInput::new_empty(Method::MaxMinRelativeHumidity);
-        Units::from_abbreviation(rh_units).expect("Invalid units for relative humidity");
         ea_input.rhmax = Some(rhmax);
         ea_input.rhmin = Some(rhmin);
-
-        let t_unit = Units::from_abbreviation(temp_units).expect("Invalid units for temperature");
-        match t_unit {
-            Units::Celsius => {
-                ea_input.tmax = Some(tmax);
-                ea_input.tmin = Some(tmin);
-            }
-            Units::Fahrenheit => {
-                ea_input.tmax = Some(
-                    Units::Fahrenheit
-                        .convert(tmax, &Units::Celsius)
-                        .expect("Units conversion failed"),
-                );
-                ea_input.tmin = Some(
-                    Units::Fahrenheit
-                        .convert(tmin, &Units::Celsius)
-                        .expect("Units conversion failed"),
-                );
-            }
-            _ => panic!("Invalid units for temperature"),
-        }
-
-        ea_input
+        ea_input.tmax = Some(to_celsius(tmax, temp_units)?);
+        ea_input.tmin = Some(to_celsius(tmin, temp_units)?);
+        Ok(ea_input)
     }
 
-    pub fn new_rhmax(rhmax: f64, rh_units: &str, tmax: f64, temp_units: &str) -> EaInput {
+    /// Creates an EaInput from daily maximum relative humidity with Tmax (Eq. 13).
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `OutOfRange` when `rhmax` is outside 0 to 100 and `InvalidUnits` when
+    ///   `rh_units` is not "%", "percent", or "fraction" or the temperature units are not
+    ///   recognized.
+    pub fn new_rhmax(
+        rhmax: f64,
+        rh_units: &str,
+        tmax: f64,
+        temp_units: &str,
+    ) -> Result<EaInput, RefEtError> {
+        check_rh_units(rh_units)?;
+        check_rh("rhmax", rhmax)?;
+
         let mut ea_input = EaInput::new_empty(Method::DailyMaxRelativeHumidity);
-        Units::from_abbreviation(rh_units).expect("Invalid units for relative humidity");
         ea_input.rhmax = Some(rhmax);
-
-        let t_unit = Units::from_abbreviation(temp_units).expect("Invalid units for temperature");
-        match t_unit {
-            Units::Celsius => {
-                ea_input.tmax = Some(tmax);
-            }
-            Units::Fahrenheit => {
-                ea_input.tmax = Some(
-                    Units::Fahrenheit
-                        .convert(tmax, &Units::Celsius)
-                        .expect("Units conversion failed"),
-                );
-            }
-            _ => panic!("Invalid units for temperature"),
-        }
-
-        ea_input
+        ea_input.tmax = Some(to_celsius(tmax, temp_units)?);
+        Ok(ea_input)
     }
 
-    pub fn new_rhmin(rhmin: f64, rh_units: &str, tmin: f64, temp_units: &str) -> EaInput {
+    /// Creates an EaInput from daily minimum relative humidity with Tmin (Eq. 12).
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `OutOfRange` when `rhmin` is outside 0 to 100 and `InvalidUnits` when
+    ///   `rh_units` is not "%", "percent", or "fraction" or the temperature units are not
+    ///   recognized.
+    pub fn new_rhmin(
+        rhmin: f64,
+        rh_units: &str,
+        tmin: f64,
+        temp_units: &str,
+    ) -> Result<EaInput, RefEtError> {
+        check_rh_units(rh_units)?;
+        check_rh("rhmin", rhmin)?;
+
         let mut ea_input = EaInput::new_empty(Method::DailyMinRelativeHumidity);
-        Units::from_abbreviation(rh_units).expect("Invalid units for relative humidity");
         ea_input.rhmin = Some(rhmin);
+        ea_input.tmin = Some(to_celsius(tmin, temp_units)?);
+        Ok(ea_input)
+    }
 
-        let t_unit = Units::from_abbreviation(temp_units).expect("Invalid units for temperature");
-        match t_unit {
-            Units::Celsius => {
-                ea_input.tmin = Some(tmin);
-            }
-            Units::Fahrenheit => {
-                ea_input.tmax = Some(
-                    Units::Fahrenheit
-                        .convert(tmin, &Units::Celsius)
-                        .expect("Units conversion failed"),
-                );
-            }
-            _ => panic!("Invalid units for temperature"),
+    /// Creates an EaInput for the Tmin fallback (Eq. E.1).
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `InvalidUnits` when the units are not a temperature.
+    pub fn new_tmin(tmin: f64, units: &str) -> Result<EaInput, RefEtError> {
+        EaInput::builder().tmin(tmin, units).build()
+    }
+
+    /// Creates an EaInput from daily mean relative humidity and daily mean air temperature, for
+    /// records that only carry pre-averaged values.
+    ///
+    /// # Arguments
+    ///
+    /// * `rh_mean` - Daily mean relative humidity in % (0 to 100). Like the other relative humidity
+    ///   methods, a value of at most 1 is read as a fraction.
+    /// * `t_mean` - Daily mean air temperature.
+    /// * `temp_units` - Units of `t_mean`, Celsius, Fahrenheit, or Kelvin.
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `OutOfRange` when `rh_mean` is outside 0 to 100 and `InvalidUnits` when
+    ///   the temperature units are not recognized.
+    pub fn new_rh_mean(rh_mean: f64, t_mean: f64, temp_units: &str) -> Result<EaInput, RefEtError> {
+        if !(0.0..=100.0).contains(&rh_mean) {
+            return Err(RefEtError::OutOfRange {
+                field: "rh_mean",
+                value: rh_mean,
+            });
         }
 
-        ea_input
-    }
+        let t_mean = to_celsius(t_mean, temp_units)?;
 
-    pub fn new_tmin(tmin: f64, units: &str) -> EaInput {
-        let mut tmin_value = 0.0;
-        if let Ok(unit) = Units::from_abbreviation(units) {
-            match unit {
-                Units::Celsius => tmin_value = tmin,
-                Units::Fahrenheit => {
-                    tmin_value = Units::Fahrenheit
-                        .convert(tmin, &Units::Celsius)
-                        .expect("Units conversion failed")
-                }
-                _ => panic!("Invalid units for tmin: {}", units),
-            }
-        } else {
-            panic!("Invalid units: {}", units)
-        };
+        let mut ea_input = EaInput::new_empty(Method::MeanRelativeHumidity);
+        ea_input.rhmean = Some(rh_mean);
+        ea_input.tmean = Some(t_mean);
+        Ok(ea_input)
+    }
 
-        EaInput {
-            input: None,
-            method: Method::DailyMinAirTemperature,
-            rhmax: None,
-            rhmin: None,
-            tmax: None,
-            tmin: Some(tmin_value), // Use the converted value here
+    /// Creates an EaInput from a single relative humidity reading and the air temperature it was
+    /// taken at, by deriving the dewpoint and using the DewPoint method.
+    ///
+    /// # Arguments
+    ///
+    /// * `rh` - Relative humidity in %, above 0 and at most 100.
+    /// * `temp` - Air temperature at the time of the reading.
+    /// * `units` - Units of `temp`, Celsius, Fahrenheit, or Kelvin.
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `OutOfRange` when `rh` is outside its range and `InvalidUnits` when the
+    ///   temperature units are not recognized.
+    pub fn new_from_rh_single(rh: f64, temp: f64, units: &str) -> Result<EaInput, RefEtError> {
+        check_rh("rh", rh)?;
+        if rh == 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "rh",
+                value: rh,
+            });
         }
+
+        let tdew = dewpoint_from_rh(rh, to_celsius(temp, units)?);
+        EaInput::new_dewpoint(tdew, "C")
+    }
+
+    /// Creates an EaInput for the Tmin fallback using `offset` in degrees Celsius in place of the
+    /// default Ko of `RefEtConfig::tmin_dewpoint_offset`.
+    pub fn new_tmin_with_offset(
+        tmin: f64,
+        units: &str,
+        offset: f64,
+    ) -> Result<EaInput, RefEtError> {
+        let mut ea_input = EaInput::new_tmin(tmin, units)?;
+        ea_input.tmin_offset = offset;
+        Ok(ea_input)
+    }
+
+    /// Creates an EaInput from daily mean air temperature alone, estimating the dewpoint as
+    /// Tmean - 2 °C.
+    ///
+    /// This is the least accurate way to estimate ea and is meant for monthly datasets that have
+    /// nothing else. Mean temperature sits well above the dewpoint in arid climates, so ea and
+    /// with it the humidity term are overestimated there; use any humidity measurement or Tmin
+    /// when one is available.
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `InvalidUnits` when the temperature units are not recognized.
+    pub fn new_tmean(tmean: f64, units: &str) -> Result<EaInput, RefEtError> {
+        EaInput::new_tmean_with_offset(tmean, units, TMEAN_DEWPOINT_OFFSET)
+    }
+
+    /// Creates an EaInput like `new_tmean` using `offset` in degrees Celsius as the humidity
+    /// deficit between the mean temperature and the dewpoint.
+    pub fn new_tmean_with_offset(
+        tmean: f64,
+        units: &str,
+        offset: f64,
+    ) -> Result<EaInput, RefEtError> {
+        let mut ea_input = EaInput::new_empty(Method::MeanAirTemperature);
+        ea_input.tmean = Some(to_celsius(tmean, units)?);
+        ea_input.tmean_offset = offset;
+        Ok(ea_input)
+    }
+
+    /// Creates an EaInput from psychrometer wet and dry bulb temperatures.
+    ///
+    /// # Arguments
+    ///
+    /// * `twet` - Wet bulb temperature.
+    /// * `tdry` - Dry bulb temperature.
+    /// * `pressure_kpa` - Atmospheric pressure in kPa.
+    /// * `apsy` - Psychrometer coefficient, 0.000662 for ventilated (Asmann type), 0.000800 for
+    ///   naturally ventilated, and 0.001200 for non-ventilated psychrometers installed indoors.
+    /// * `units` - Units of the temperatures, Celsius or Fahrenheit.
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `InvalidUnits` when the temperature units are not recognized.
+    pub fn new_wet_dry_bulb(
+        twet: f64,
+        tdry: f64,
+        pressure_kpa: f64,
+        apsy: f64,
+        units: &str,
+    ) -> Result<EaInput, RefEtError> {
+        let mut ea_input = EaInput::new_empty(Method::WetDryBulb);
+        ea_input.twet = Some(to_celsius(twet, units)?);
+        ea_input.tdry = Some(to_celsius(tdry, units)?);
+        ea_input.pressure = Some(pressure_kpa);
+        ea_input.apsy = Some(apsy);
+        Ok(ea_input)
     }
 
     // ea is a method to return the ea that can be used in the various parts of the app
-    pub fn ea(&self) -> Result<f64, Box<dyn Error>> {
+    pub fn ea(&self) -> Result<f64, RefEtError> {
         let ea = match self.method {
             Method::Direct => self.get_ea()?,
             Method::DewPoint => self.convert_from_tdew()?,
@@ -235,6 +442,9 @@ impl EaInput {
             Method::DailyMaxRelativeHumidity => self.convert_rhmax()?,
             Method::DailyMinRelativeHumidity => self.convert_rhmin()?,
             Method::DailyMinAirTemperature => self.convert_from_tmin()?,
+            Method::MeanRelativeHumidity => self.convert_rh_mean()?,
+            Method::WetDryBulb => self.convert_from_wet_dry()?,
+            Method::MeanAirTemperature => self.convert_from_tmean()?,
         };
 
         Ok(ea)
@@ -245,29 +455,37 @@ impl EaInput {
         0.6108 * E.powf((17.27 * t) / (t + 237.3))
     }
 
-    fn get_ea(&self) -> Result<f64, Box<dyn Error>> {
-        let value = self.input.ok_or("must have an input value")?;
+    fn get_ea(&self) -> Result<f64, RefEtError> {
+        let value = self.input.ok_or(RefEtError::MissingField("ea"))?;
         Ok(value)
     }
 
-    fn convert_from_tdew(&self) -> Result<f64, Box<dyn Error>> {
-        let value = self.input.ok_or("must have an input value")?;
+    fn convert_from_tdew(&self) -> Result<f64, RefEtError> {
+        let value = self.input.ok_or(RefEtError::MissingField("dewpoint"))?;
         let ea = Self::eo(value); // Eq. 8
         Ok(ea)
     }
 
     // creates a saturation vapor pressure using the minimum temperature found in Appendix E: Equation E1
-    fn convert_from_tmin(&self) -> Result<f64, Box<dyn Error>> {
-        let tmin_v = self.tmin.ok_or("tmin must be a valid input")?;
-        let ea = Self::eo(tmin_v - 3.0); // Eq. 8
+    // where Tdew = Tmin - Ko, then ea is the saturation vapor pressure at that dewpoint
+    fn convert_from_tmin(&self) -> Result<f64, RefEtError> {
+        let tmin_v = self.tmin.ok_or(RefEtError::MissingField("tmin"))?;
+        let ea = Self::eo(tmin_v - self.tmin_offset); // Eq. E.1 and Eq. 8
+        Ok(ea)
+    }
+
+    // the mean temperature analog of Eq. E.1, Tdew = Tmean - offset
+    fn convert_from_tmean(&self) -> Result<f64, RefEtError> {
+        let tmean_v = self.tmean.ok_or(RefEtError::MissingField("tmean"))?;
+        let ea = Self::eo(tmean_v - self.tmean_offset); // Eq. 8
         Ok(ea)
     }
 
-    fn convert_min_max_rh(&self) -> Result<f64, Box<dyn Error>> {
-        let tmax_v = self.tmax.ok_or("tmax must be a valid input")?;
-        let tmin_v = self.tmin.ok_or("tmin must be a valid input")?;
-        let rhmax = self.rhmax.ok_or("RHmax must have valid value")?;
-        let rhmin = self.rhmin.ok_or("RHmin must have valid value")?;
+    fn convert_min_max_rh(&self) -> Result<f64, RefEtError> {
+        let tmax_v = self.tmax.ok_or(RefEtError::MissingField("tmax"))?;
+        let tmin_v = self.tmin.ok_or(RefEtError::MissingField("tmin"))?;
+        let rhmax = self.rhmax.ok_or(RefEtError::MissingField("rhmax"))?;
+        let rhmin = self.rhmin.ok_or(RefEtError::MissingField("rhmin"))?;
 
         let rhmax = if rhmax > 1.0 { rhmax / 100.0 } else { rhmax };
         let rhmin = if rhmin > 1.0 { rhmin / 100.0 } else { rhmin };
@@ -276,18 +494,37 @@ impl EaInput {
         Ok(ea)
     }
 
-    fn convert_rhmin(&self) -> Result<f64, Box<dyn Error>> {
-        let tmin_v = self.tmin.ok_or("tmin must be a valid input")?;
-        let rhmin = self.rhmin.ok_or("RHmin must have valid value")?;
+    fn convert_rhmin(&self) -> Result<f64, RefEtError> {
+        let tmin_v = self.tmin.ok_or(RefEtError::MissingField("tmin"))?;
+        let rhmin = self.rhmin.ok_or(RefEtError::MissingField("rhmin"))?;
         let rhmin = if rhmin > 1.0 { rhmin / 100.0 } else { rhmin };
 
         let ea = Self::eo(tmin_v) * rhmin; // Eq. 12
         Ok(ea)
     }
 
-    fn convert_rhmax(&self) -> Result<f64, Box<dyn Error>> {
-        let tmax_v = self.tmax.ok_or("tmax must be a valid input")?;
-        let rhmax = self.rhmax.ok_or("RHmax must have valid value")?;
+    fn convert_rh_mean(&self) -> Result<f64, RefEtError> {
+        let tmean_v = self.tmean.ok_or(RefEtError::MissingField("tmean"))?;
+        let rhmean = self.rhmean.ok_or(RefEtError::MissingField("rhmean"))?;
+        let rhmean = if rhmean > 1.0 { rhmean / 100.0 } else { rhmean };
+
+        let ea = Self::eo(tmean_v) * rhmean; // FAO-56 Eq. 19
+        Ok(ea)
+    }
+
+    fn convert_from_wet_dry(&self) -> Result<f64, RefEtError> {
+        let twet = self.twet.ok_or(RefEtError::MissingField("twet"))?;
+        let tdry = self.tdry.ok_or(RefEtError::MissingField("tdry"))?;
+        let pressure = self.pressure.ok_or(RefEtError::MissingField("pressure"))?;
+        let apsy = self.apsy.ok_or(RefEtError::MissingField("apsy"))?;
+
+        let ea = Self::eo(twet) - apsy * pressure * (tdry - twet); // Eq. 14 and 15
+        Ok(ea)
+    }
+
+    fn convert_rhmax(&self) -> Result<f64, RefEtError> {
+        let tmax_v = self.tmax.ok_or(RefEtError::MissingField("tmax"))?;
+        let rhmax = self.rhmax.ok_or(RefEtError::MissingField("rhmax"))?;
         let rhmax = if rhmax > 1.0 { rhmax / 100.0 } else { rhmax };
 
         let ea = Self::eo(tmax_v) * rhmax; // Eq. 13
@@ -295,6 +532,139 @@ impl EaInput {
     }
 }
 
+/// Collects the humidity and temperature values of a day and picks the ea method when built, in
+/// the priority order of `EaInput::new_from_output`: ea, dewpoint, RHmax and RHmin, then the Tmin
+/// fallback. Units and required combinations are only checked by `build`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EaInputBuilder {
+    ea: Option<(f64, String)>,       // actual vapor pressure and its units
+    dewpoint: Option<(f64, String)>, // dewpoint temperature and its units
+    rh: Option<(f64, f64, String)>,  // daily maximum and minimum relative humidity and their units
+    rh_mean: Option<f64>,            // daily mean relative humidity in %
+    tmax: Option<(f64, String)>,     // daily maximum air temperature and its units
+    tmin: Option<(f64, String)>,     // daily minimum air temperature and its units
+}
+
+impl EaInputBuilder {
+    /// Sets the measured actual vapor pressure in "kPa" or "Pa".
+    pub fn direct(mut self, ea: f64, units: &str) -> EaInputBuilder {
+        self.ea = Some((ea, units.to_string()));
+        self
+    }
+
+    /// Sets the dewpoint temperature in "C", "F", or "K".
+    pub fn dewpoint(mut self, tdew: f64, units: &str) -> EaInputBuilder {
+        self.dewpoint = Some((tdew, units.to_string()));
+        self
+    }
+
+    /// Sets the daily maximum and minimum relative humidity, which also need `temperatures`.
+    pub fn rh_max_min(mut self, rhmax: f64, rhmin: f64, units: &str) -> EaInputBuilder {
+        self.rh = Some((rhmax, rhmin, units.to_string()));
+        self
+    }
+
+    /// Sets the daily mean relative humidity in %, which also needs `temperatures` for the mean
+    /// air temperature. It is used when RHmax and RHmin aren't set.
+    pub fn rh_mean(mut self, rh_mean: f64) -> EaInputBuilder {
+        self.rh_mean = Some(rh_mean);
+        self
+    }
+
+    /// Sets the daily maximum and minimum air temperatures in "C", "F", or "K".
+    pub fn temperatures(mut self, tmax: f64, tmin: f64, units: &str) -> EaInputBuilder {
+        self.tmax = Some((tmax, units.to_string()));
+        self.tmin = Some((tmin, units.to_string()));
+        self
+    }
+
+    /// Sets only the daily minimum air temperature in "C", "F", or "K", for the Tmin fallback.
+    pub fn tmin(mut self, tmin: f64, units: &str) -> EaInputBuilder {
+        self.tmin = Some((tmin, units.to_string()));
+        self
+    }
+
+    /// Validates the values and creates the EaInput with the best available method.
+    ///
+    /// # Returns
+    ///
+    /// * The EaInput, or `InvalidUnits` for units that don't fit their value, `OutOfRange` for a
+    ///   relative humidity outside 0 to 100, and `MissingField` when relative humidity is set
+    ///   without temperatures or nothing is set at all. The methods are tried in the order ea,
+    ///   dewpoint, RHmax and RHmin, mean RH, and Tmin.
+    pub fn build(&self) -> Result<EaInput, RefEtError> {
+        let celsius = |value: &Option<(f64, String)>| {
+            value
+                .as_ref()
+                .map(|(value, units)| to_celsius(*value, units))
+                .transpose()
+        };
+        let tmax = celsius(&self.tmax)?;
+        let tmin = celsius(&self.tmin)?;
+        let dewpoint = celsius(&self.dewpoint)?;
+
+        if let Some((ea, units)) = &self.ea {
+            let ea = match Units::from_abbreviation(units) {
+                Ok(Units::KiloPascals) => *ea,
+                Ok(Units::Pascals) => pa_to_kpa(*ea),
+                _ => return Err(RefEtError::InvalidUnits(units.to_string())),
+            };
+            let mut ea_input = EaInput::new_empty(Method::Direct);
+            ea_input.input = Some(ea);
+            Ok(ea_input)
+        } else if let Some(tdew) = dewpoint {
+            let mut ea_input = EaInput::new_empty(Method::DewPoint);
+            ea_input.input = Some(tdew);
+            Ok(ea_input)
+        } else if let Some((rhmax, rhmin, units)) = &self.rh {
+            EaInput::new_rhmax_min(
+                *rhmax,
+                *rhmin,
+                units,
+                tmax.ok_or(RefEtError::MissingField("tmax"))?,
+                tmin.ok_or(RefEtError::MissingField("tmin"))?,
+                "C",
+            )
+        } else if let Some(rh_mean) = self.rh_mean {
+            let tmax = tmax.ok_or(RefEtError::MissingField("tmax"))?;
+            let tmin = tmin.ok_or(RefEtError::MissingField("tmin"))?;
+            EaInput::new_rh_mean(rh_mean, (tmax + tmin) / 2.0, "C")
+        } else if let Some(tmin) = tmin {
+            let mut ea_input = EaInput::new_empty(Method::DailyMinAirTemperature);
+            ea_input.tmin = Some(tmin);
+            Ok(ea_input)
+        } else {
+            Err(RefEtError::MissingField("ea"))
+        }
+    }
+}
+
+/// Prints the selected method and the ea it produces, or the error when ea can't be calculated.
+impl fmt::Display for EaInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ea() {
+            Ok(ea) => write!(f, "{:?}: ea = {:.4} kPa", self.method, ea),
+            Err(error) => write!(f, "{:?}: {}", self.method, error),
+        }
+    }
+}
+
+fn check_rh(field: &'static str, value: f64) -> Result<(), RefEtError> {
+    if !(0.0..=100.0).contains(&value) {
+        return Err(RefEtError::OutOfRange { field, value });
+    }
+    Ok(())
+}
+
+/// Relative humidity is unitless, so only "%", "percent", and "fraction" are accepted. Values of
+/// at most 1 are read as a fraction either way.
+fn check_rh_units(units: &str) -> Result<(), RefEtError> {
+    match units.trim().to_lowercase().as_str() {
+        "%" | "percent" | "fraction" => Ok(()),
+        _ => Err(RefEtError::InvalidUnits(units.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,7 +672,7 @@ mod tests {
     #[test]
     fn test_ea_method_1_ea() {
         // let input = Value::new(1000.0, "pa".to_string());
-        let ea_input = EaInput::new_direct(1000.0, "pa");
+        let ea_input = EaInput::new_direct(1000.0, "pa").unwrap();
         // let ea_input = EaInput::new(Some(input), Direct, None, None, None, None);
 
         let result = ea_input.ea();
@@ -310,7 +680,7 @@ mod tests {
         assert_eq!(result.unwrap(), 1.0);
 
         // let input = Value::new(1.2, "kpa".to_string());
-        let ea_input = EaInput::new_direct(1.2, "kpa");
+        let ea_input = EaInput::new_direct(1.2, "kpa").unwrap();
         // let ea_input = EaInput::new(Some(input), Direct, None, None, None, None);
 
         let result = ea_input.ea();
@@ -318,7 +688,7 @@ mod tests {
         assert!((result.unwrap() - 1.2).abs() < 0.0001);
 
         // let input = Value::new(3.2, "kpa".to_string());
-        let ea_input = EaInput::new_direct(3.2, "kpa");
+        let ea_input = EaInput::new_direct(3.2, "kpa").unwrap();
         // let ea_input = EaInput::new(Some(input), Direct, None, None, None, None);
 
         let result = ea_input.ea();
@@ -326,7 +696,7 @@ mod tests {
         assert_eq!(result.unwrap(), 3.2);
 
         // let input = Value::new(2853.0, "pa".to_string());
-        let ea_input = EaInput::new_direct(2853.0, "pa");
+        let ea_input = EaInput::new_direct(2853.0, "pa").unwrap();
         // let ea_input = EaInput::new(Some(input), Direct, None, None, None, None);
 
         let result = ea_input.ea();
@@ -337,7 +707,7 @@ mod tests {
     #[test]
     fn test_ea_method_2_dew() {
         // let input = Value::new(10.0, "c".to_string());
-        let ea_input = EaInput::new_dewpoint(10.0, "c");
+        let ea_input = EaInput::new_dewpoint(10.0, "c").unwrap();
         // let ea_input = EaInput::new(Some(input), DewPoint, None, None, None, None);
 
         let result = ea_input.ea();
@@ -345,7 +715,7 @@ mod tests {
         assert!((result.unwrap() - 1.228).abs() < 0.0001);
 
         // let input = Value::new(65.0, "f".to_string());
-        let ea_input = EaInput::new_dewpoint(65.0, "f");
+        let ea_input = EaInput::new_dewpoint(65.0, "f").unwrap();
         // let ea_input = EaInput::new(Some(input), DewPoint, None, None, None, None);
 
         let result = ea_input.ea();
@@ -358,7 +728,7 @@ mod tests {
         // let t_max = Value::new(32.0, "c".to_string());
         // let t_min = Value::new(25.0, "C".to_string());
 
-        let ea_input = EaInput::new_rhmax_min(75.0, 45.0, "%", 32.0, 25.0, "c");
+        let ea_input = EaInput::new_rhmax_min(75.0, 45.0, "%", 32.0, 25.0, "c").unwrap();
         // let ea_input = EaInput::new(None, MaxMinRelativeHumidity, Some(75.0), Some(45.0), Some(t_max), Some(t_min));
 
         let result = ea_input.ea();
@@ -368,7 +738,7 @@ mod tests {
         // let t_max = Value::new(29.0, "c".to_string());
         // let t_min = Value::new(20.0, "c".to_string());
 
-        let ea_input = EaInput::new_rhmax_min(85.0, 65.0, "%", 29.0, 20.0, "c");
+        let ea_input = EaInput::new_rhmax_min(85.0, 65.0, "%", 29.0, 20.0, "c").unwrap();
         // let ea_input = EaInput::new(None, MaxMinRelativeHumidity, Some(85.0), Some(65.0), Some(t_max), Some(t_min));
 
         let result = ea_input.ea();
@@ -376,10 +746,24 @@ mod tests {
         assert!((result.unwrap() - 2.2956).abs() < 0.0001);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ea_input_json_round_trip() {
+        // Given
+        let ea_input = EaInput::new_rhmax_min(82.0, 17.0, "%", 32.4, 10.9, "c").unwrap();
+
+        // When
+        let json = serde_json::to_string(&ea_input).unwrap();
+        let restored: EaInput = serde_json::from_str(&json).unwrap();
+
+        assert!(json.contains("MaxMinRelativeHumidity"));
+        assert_eq!(restored.ea().unwrap(), ea_input.ea().unwrap());
+    }
+
     #[test]
     fn test_ea_method_6_rh_max() {
         // let t_min = Value::new(25.0, "c".to_string());
-        let ea_input = EaInput::new_rhmax(75.0, "%", 25.0, "c");
+        let ea_input = EaInput::new_rhmax(75.0, "%", 25.0, "c").unwrap();
         // let ea_input = EaInput::new(None, DailyMaxRelativeHumidity, Some(75.0), None, None, Some(t_min));
 
         let result = ea_input.ea();
@@ -387,7 +771,7 @@ mod tests {
         assert!((result.unwrap() - 2.3758).abs() < 0.0001);
 
         // let t_min = Value::new(20.0, "c".to_string());
-        let ea_input = EaInput::new_rhmax(85.0, "%", 20.0, "c");
+        let ea_input = EaInput::new_rhmax(85.0, "%", 20.0, "c").unwrap();
         // let ea_input = EaInput::new(None, DailyMaxRelativeHumidity, Some(85.0), None, None, Some(t_min));
 
         let result = ea_input.ea();
@@ -398,7 +782,7 @@ mod tests {
     #[test]
     fn test_ea_method_7_rh_min() {
         // let t_max = Value::new(32.0, "c".to_string());
-        let ea_input = EaInput::new_rhmin(45.0, "percent", 32.0, "c");
+        let ea_input = EaInput::new_rhmin(45.0, "percent", 32.0, "c").unwrap();
         // let ea_input = EaInput::new(None, DailyMinRelativeHumidity, None, Some(45.0), Some(t_max), None);
 
         let result = ea_input.ea();
@@ -408,11 +792,415 @@ mod tests {
         assert!((result.unwrap() - 2.1396).abs() < 0.0001);
 
         // let t_max = Value::new(29.0, "c".to_string());
-        let ea_input = EaInput::new_rhmin(65.0, "percent", 29.0, "c");
+        let ea_input = EaInput::new_rhmin(65.0, "percent", 29.0, "c").unwrap();
         // let ea_input = EaInput::new(None, DailyMinRelativeHumidity, None, Some(65.0), Some(t_max), None);
 
         let result = ea_input.ea();
         assert!(result.is_ok());
         assert!((result.unwrap() - 2.6036).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_ea_method_7_rh_min_fahrenheit() {
+        // Given, 89.6 F is 32 C
+        let celsius = EaInput::new_rhmin(45.0, "percent", 32.0, "c").unwrap();
+        let fahrenheit = EaInput::new_rhmin(45.0, "percent", 89.6, "f").unwrap();
+
+        // When
+        let result = fahrenheit.ea();
+
+        assert!((result.unwrap() - celsius.ea().unwrap()).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_ea_from_rh_single() {
+        // Given
+        let rh_ea = EaInput::new_rhmin(45.0, "percent", 32.0, "c").unwrap();
+
+        // When
+        let single = EaInput::new_from_rh_single(45.0, 89.6, "f").unwrap();
+
+        assert!((single.ea().unwrap() - rh_ea.ea().unwrap()).abs() < 0.0001);
+        assert!(EaInput::new_from_rh_single(0.0, 32.0, "c").is_err());
+        assert!(EaInput::new_from_rh_single(120.0, 32.0, "c").is_err());
+    }
+
+    #[test]
+    fn test_ea_rh_out_of_range() {
+        assert!(matches!(
+            EaInput::new_rhmax(750.0, "%", 25.0, "c"),
+            Err(RefEtError::OutOfRange { field: "rhmax", .. })
+        ));
+        assert!(EaInput::new_rhmin(-5.0, "%", 25.0, "c").is_err());
+        assert!(EaInput::new_rhmax_min(750.0, 45.0, "%", 32.0, 25.0, "c").is_err());
+    }
+
+    #[test]
+    fn test_ea_rhmax_below_rhmin() {
+        assert_eq!(
+            EaInput::new_rhmax_min(40.0, 80.0, "%", 32.0, 25.0, "c").err(),
+            Some(RefEtError::OutOfRange {
+                field: "rhmax",
+                value: 40.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_ea_missing_value_reports_field() {
+        assert_eq!(
+            EaInput::new_empty(Method::DewPoint).ea(),
+            Err(RefEtError::MissingField("dewpoint"))
+        );
+        assert_eq!(
+            EaInput::new_empty(Method::MaxMinRelativeHumidity).ea(),
+            Err(RefEtError::MissingField("tmax"))
+        );
+    }
+
+    #[test]
+    fn test_ea_method_wet_dry_bulb() {
+        // Given, FAO-56 Example 4 with an Asmann psychrometer at 1200 m
+        let ea_input = EaInput::new_wet_dry_bulb(16.0, 20.0, 87.9, 0.000662, "c").unwrap();
+
+        // When
+        let result = ea_input.ea();
+
+        // FAO-56 gives 1.58 kPa
+        assert!((result.unwrap() - 1.585).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_ea_method_tmin_offsets() {
+        // Given
+        let tmin = 10.9;
+
+        // When
+        let ea_0 = EaInput::new_tmin_with_offset(tmin, "c", 0.0)
+            .unwrap()
+            .ea()
+            .unwrap();
+        let ea_2 = EaInput::new_tmin_with_offset(tmin, "c", 2.0)
+            .unwrap()
+            .ea()
+            .unwrap();
+        let ea_3 = EaInput::new_tmin_with_offset(tmin, "c", 3.0)
+            .unwrap()
+            .ea()
+            .unwrap();
+
+        // humid sites use Tmin directly as the dewpoint, arid sites subtract 2 to 4 degrees
+        assert!((ea_0 - 1.3040).abs() < 0.0001);
+        assert!((ea_2 - 1.1403).abs() < 0.0001);
+        assert!((ea_3 - 1.0655).abs() < 0.0001);
+        assert!(ea_0 > ea_2 && ea_2 > ea_3);
+    }
+
+    #[test]
+    fn test_ea_method_tmin_default_offset() {
+        let default_ea = EaInput::new_tmin(10.9, "c").unwrap().ea().unwrap();
+        let configured_ea =
+            EaInput::new_tmin_with_offset(10.9, "c", RefEtConfig::default().tmin_dewpoint_offset)
+                .unwrap()
+                .ea()
+                .unwrap();
+
+        assert_eq!(default_ea, configured_ea);
+    }
+
+    #[test]
+    fn test_ea_method_rh_mean() {
+        // Given
+        let rh_mean = 55.0;
+        let t_mean = 20.0;
+
+        // When
+        let ea_input = EaInput::new_rh_mean(rh_mean, t_mean, "c").unwrap();
+
+        let result = ea_input.ea();
+        assert!(result.is_ok());
+        assert!((result.unwrap() - 1.2861).abs() < 0.0001);
+
+        // a mean RH of 0.8 is read as a fraction, the same as 80%
+        let fraction = EaInput::new_rh_mean(0.8, 293.15, "K")
+            .unwrap()
+            .ea()
+            .unwrap();
+        let percent = EaInput::new_rh_mean(80.0, 20.0, "C").unwrap().ea().unwrap();
+        assert!((fraction - 1.8706).abs() < 0.0001);
+        assert!((fraction - percent).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_builder_selects_rh_mean_before_tmin() {
+        // Given
+        let builder = EaInput::builder()
+            .rh_mean(55.0)
+            .temperatures(32.4, 10.9, "C");
+
+        // When
+        let ea_input = builder.build().unwrap();
+
+        assert!(matches!(ea_input.method, Method::MeanRelativeHumidity));
+        assert!((ea_input.ea().unwrap() - 1.4234).abs() < 0.0001);
+        assert!(matches!(
+            builder.rh_max_min(82.0, 17.0, "%").build().unwrap().method,
+            Method::MaxMinRelativeHumidity
+        ));
+    }
+
+    #[test]
+    fn test_ea_method_tmean() {
+        // Given
+        let t_mean = 20.0;
+
+        // When
+        let default = EaInput::new_tmean(t_mean, "C").unwrap().ea().unwrap();
+        let humid = EaInput::new_tmean_with_offset(68.0, "F", 0.0)
+            .unwrap()
+            .ea()
+            .unwrap();
+
+        // Tdew of 18 C with the default offset, and Tdew equal to Tmean without one
+        assert!((default - 2.0640).abs() < 0.0001);
+        assert!((humid - 2.3383).abs() < 0.0001);
+        assert!(EaInput::new_tmean(t_mean, "kPa").is_err());
+    }
+
+    #[test]
+    fn test_ea_input_display() {
+        // Given
+        let ea_input = EaInput::new_rhmax_min(82.0, 30.0, "%", 32.4, 10.9, "C").unwrap();
+
+        // When
+        let text = ea_input.to_string();
+
+        assert!(text.starts_with("MaxMinRelativeHumidity: ea = "));
+        let ea: f64 = text
+            .trim_start_matches("MaxMinRelativeHumidity: ea = ")
+            .trim_end_matches(" kPa")
+            .parse()
+            .unwrap();
+        assert!((ea - ea_input.ea().unwrap()).abs() < 0.0001);
+        assert!(format!("{:?}", ea_input).contains("MaxMinRelativeHumidity"));
+        assert!(EaInput::new_empty(Method::DewPoint)
+            .to_string()
+            .starts_with("DewPoint: "));
+    }
+
+    fn output_with_dewpoint_and_rh() -> Output {
+        Output::new_with_values(
+            32.4,
+            10.9,
+            Some(82.0),
+            Some(30.0),
+            Some(10.0),
+            None,
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_new_from_output_with_method_forced() {
+        // Given, an output with both dewpoint and RHmax/RHmin
+        let output = output_with_dewpoint_and_rh();
+
+        // When
+        let dewpoint = EaInput::new_from_output_with_method(&output, Method::DewPoint).unwrap();
+        let rh =
+            EaInput::new_from_output_with_method(&output, Method::MaxMinRelativeHumidity).unwrap();
+
+        assert!(matches!(dewpoint.method, Method::DewPoint));
+        assert!((dewpoint.ea().unwrap() - 1.2280).abs() < 0.0001);
+        assert!(matches!(rh.method, Method::MaxMinRelativeHumidity));
+        let expected = EaInput::new_rhmax_min(82.0, 30.0, "%", 32.4, 10.9, "C").unwrap();
+        assert_eq!(rh.ea().unwrap(), expected.ea().unwrap());
+    }
+
+    #[test]
+    fn test_new_from_output_with_method_uses_config() {
+        // Given, an output in Fahrenheit with a configured Tmin dewpoint offset
+        let output = Output::new_with_values(
+            90.32,
+            51.62,
+            None,
+            None,
+            None,
+            None,
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+        let config = RefEtConfig {
+            temperature_units: "F".to_string(),
+            tmin_dewpoint_offset: 2.0,
+            ..RefEtConfig::default()
+        };
+
+        // When
+        let ea_input = EaInput::new_from_output_with_method_with_config(
+            &output,
+            Method::DailyMinAirTemperature,
+            &config,
+        )
+        .unwrap();
+
+        let expected = EaInput::new_tmin_with_offset(10.9, "C", 2.0).unwrap();
+        assert!((ea_input.ea().unwrap() - expected.ea().unwrap()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_new_from_output_with_method_missing_data() {
+        // Given
+        let output = output_with_dewpoint_and_rh();
+
+        // When
+        let result = EaInput::new_from_output_with_method(&output, Method::Direct);
+
+        assert_eq!(result.err(), Some(RefEtError::MissingField("ea")));
+        assert!(matches!(
+            EaInput::new_from_output_with_method(&output, Method::WetDryBulb),
+            Err(RefEtError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_builder_picks_method() {
+        // Given
+        let builder = EaInput::builder()
+            .rh_max_min(82.0, 30.0, "%")
+            .temperatures(32.4, 10.9, "C");
+
+        // When
+        let rh = builder.build().unwrap();
+        let dewpoint = builder.clone().dewpoint(10.0, "C").build().unwrap();
+        let direct = builder.direct(1270.0, "Pa").build().unwrap();
+
+        assert!(matches!(rh.method, Method::MaxMinRelativeHumidity));
+        assert!(matches!(dewpoint.method, Method::DewPoint));
+        assert!((direct.ea().unwrap() - 1.27).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_builder_invalid_units_err() {
+        assert_eq!(
+            EaInput::builder().direct(1.2, "C").build().err(),
+            Some(RefEtError::InvalidUnits("C".to_string()))
+        );
+        assert!(EaInput::builder().dewpoint(10.0, "kPa").build().is_err());
+        assert!(EaInput::builder()
+            .direct(1.2, "kPa")
+            .temperatures(30.0, 10.0, "furlongs")
+            .build()
+            .is_err());
+        assert_eq!(
+            EaInput::builder().rh_max_min(82.0, 30.0, "%").build().err(),
+            Some(RefEtError::MissingField("tmax"))
+        );
+        assert_eq!(
+            EaInput::builder().build().err(),
+            Some(RefEtError::MissingField("ea"))
+        );
+    }
+
+    #[test]
+    fn test_constructors_invalid_units_err() {
+        assert_eq!(
+            EaInput::new_direct(1.0, "furlongs").err(),
+            Some(RefEtError::InvalidUnits("furlongs".to_string()))
+        );
+        assert!(EaInput::new_dewpoint(10.0, "kPa").is_err());
+        assert!(EaInput::new_tmin(10.9, "furlongs").is_err());
+        assert!(EaInput::new_tmin_with_offset(10.9, "furlongs", 2.0).is_err());
+        assert!(EaInput::new_rhmax_min(82.0, 30.0, "%", 32.4, 10.9, "furlongs").is_err());
+    }
+
+    #[test]
+    fn test_temperature_units_rejected_for_rh() {
+        assert_eq!(
+            EaInput::new_rhmax_min(82.0, 30.0, "C", 32.4, 10.9, "C").err(),
+            Some(RefEtError::InvalidUnits("C".to_string()))
+        );
+        assert!(EaInput::new_rhmax(82.0, "F", 32.4, "C").is_err());
+        assert!(EaInput::new_rhmin(30.0, "K", 10.9, "C").is_err());
+        assert!(EaInput::builder()
+            .rh_max_min(82.0, 30.0, "C")
+            .temperatures(32.4, 10.9, "C")
+            .build()
+            .is_err());
+        assert!(EaInput::new_rhmin(0.3, "fraction", 10.9, "C").is_ok());
+    }
+
+    #[test]
+    fn test_ea_method_rh_mean_out_of_range() {
+        assert!(matches!(
+            EaInput::new_rh_mean(155.0, 20.0, "c"),
+            Err(RefEtError::OutOfRange {
+                field: "rh_mean",
+                ..
+            })
+        ));
+        assert!(EaInput::new_rh_mean(-1.0, 20.0, "c").is_err());
+    }
+
+    #[test]
+    fn test_ea_from_fahrenheit_output_rh_max_min() {
+        // Given
+        let date = chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let latitude = 40.41_f64.to_radians();
+        let celsius_output = Output::new_with_values(
+            32.0,
+            25.0,
+            Some(75.0),
+            Some(45.0),
+            None,
+            None,
+            None,
+            None,
+            Some(2.0),
+            1462.4,
+            latitude,
+            date,
+        );
+        let fahrenheit_output = Output::new_with_values(
+            89.6,
+            77.0,
+            Some(75.0),
+            Some(45.0),
+            None,
+            None,
+            None,
+            None,
+            Some(2.0),
+            1462.4,
+            latitude,
+            date,
+        );
+        let fahrenheit_config = RefEtConfig {
+            temperature_units: "F".to_string(),
+            ..RefEtConfig::default()
+        };
+
+        // When
+        let celsius_ea = EaInput::new_from_output(&celsius_output)
+            .unwrap()
+            .ea()
+            .unwrap();
+        let fahrenheit_ea =
+            EaInput::new_from_output_with_config(&fahrenheit_output, &fahrenheit_config)
+                .unwrap()
+                .ea()
+                .unwrap();
+
+        assert!((celsius_ea - 2.2577).abs() < 0.0001);
+        assert!((fahrenheit_ea - celsius_ea).abs() < 0.0001);
+    }
 }
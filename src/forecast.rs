@@ -0,0 +1,135 @@
+use crate::et::{calc_ra, calc_rs_from_cloud_cover, calc_rso, check_cloud_cover};
+use crate::{EaInput, RefEtError, Station};
+use chrono::{Datelike, NaiveDate};
+
+/// One day of a weather forecast, in metric units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForecastDay {
+    pub date: NaiveDate,       // forecast day
+    pub tmax: f64,             // forecast maximum air temperature in Celsius
+    pub tmin: f64,             // forecast minimum air temperature in Celsius
+    pub rh: Option<f64>,       // forecast daily mean relative humidity in %, or a fraction up to 1
+    pub dewpoint: Option<f64>, // forecast dewpoint in Celsius, used before rh
+    pub ws: f64,               // forecast wind speed in m/s at the station's wind height
+    pub cloud_cover: f64,      // forecast daily cloud cover as a fraction, oktas, or a percentage
+}
+
+/// Calculates the short and tall reference et for each day of a forecast at a station, such as a
+/// 7-day outlook for irrigation scheduling.
+///
+/// Forecasts carry cloud cover rather than solar radiation, so Rs is estimated from it with
+/// `calc_rs_from_cloud_cover`. Humidity comes from the dewpoint, then the mean
+/// relative humidity, then the Tmin dewpoint estimate when neither is forecast.
+///
+/// # Arguments
+///
+/// * `days` - The forecast days.
+/// * `station` - The station the forecast is for.
+///
+/// # Returns
+///
+/// * The short and tall reference ET of each day in order, or that day's error, such as
+///   `OutOfRange` for a cloud cover outside 0 to 100.
+pub fn forecast_et(days: &[ForecastDay], station: &Station) -> Vec<Result<(f64, f64), RefEtError>> {
+    days.iter()
+        .map(|day| forecast_day_et(day, station))
+        .collect()
+}
+
+fn forecast_day_et(day: &ForecastDay, station: &Station) -> Result<(f64, f64), RefEtError> {
+    check_cloud_cover(day.cloud_cover)?;
+
+    let ea = match (day.dewpoint, day.rh) {
+        (Some(dewpoint), _) => EaInput::builder().dewpoint(dewpoint, "C").build()?,
+        (None, Some(rh)) => EaInput::new_rh_mean(rh, (day.tmax + day.tmin) / 2.0, "C")?,
+        (None, None) => EaInput::builder().tmin(day.tmin, "C").build()?,
+    };
+
+    let ra = calc_ra(station.latitude_rad(), day.date.ordinal());
+    let rso = calc_rso(ra, station.elevation_m());
+    let rs = calc_rs_from_cloud_cover(day.cloud_cover, rso);
+
+    station.ref_et(
+        &day.date.format("%Y-%m-%d").to_string(),
+        day.tmax,
+        day.tmin,
+        ea,
+        Some(rs),
+        day.ws,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forecast_day(day: u32, cloud_cover: f64) -> ForecastDay {
+        ForecastDay {
+            date: NaiveDate::from_ymd_opt(2000, 7, day).unwrap(),
+            tmax: 32.4,
+            tmin: 10.9,
+            rh: None,
+            dewpoint: Some(10.0),
+            ws: 1.94,
+            cloud_cover,
+        }
+    }
+
+    #[test]
+    fn test_forecast_three_days() {
+        // Given, a clearing sky over Greeley with the last day missing humidity
+        let station = Station::new(40.41_f64.to_radians(), 1462.4, 3.0).unwrap();
+        let days = [
+            forecast_day(1, 0.8),
+            forecast_day(2, 0.4),
+            ForecastDay {
+                dewpoint: None,
+                rh: Some(35.0),
+                ..forecast_day(3, 0.0)
+            },
+        ];
+
+        // When
+        let outlook = forecast_et(&days, &station);
+
+        assert_eq!(outlook.len(), 3);
+        let (cloudy, _) = outlook[0].clone().unwrap();
+        let (partly, _) = outlook[1].clone().unwrap();
+        let (clear, clear_tall) = outlook[2].clone().unwrap();
+        assert!(cloudy < partly && partly < clear);
+        assert!(clear_tall > clear);
+    }
+
+    #[test]
+    fn test_forecast_cloud_cover_in_oktas_and_percent() {
+        let station = Station::new(40.41_f64.to_radians(), 1462.4, 3.0).unwrap();
+
+        let outlook = forecast_et(
+            &[
+                forecast_day(1, 0.75),
+                forecast_day(1, 6.0),
+                forecast_day(1, 75.0),
+            ],
+            &station,
+        );
+
+        let fraction = outlook[0].clone().unwrap();
+        assert_eq!(outlook[1].clone().unwrap(), fraction);
+        assert_eq!(outlook[2].clone().unwrap(), fraction);
+    }
+
+    #[test]
+    fn test_forecast_rejects_bad_cloud_cover() {
+        let station = Station::new(40.41_f64.to_radians(), 1462.4, 3.0).unwrap();
+
+        let outlook = forecast_et(&[forecast_day(1, 120.0)], &station);
+
+        assert!(matches!(
+            outlook[0],
+            Err(RefEtError::OutOfRange {
+                field: "cloud_cover",
+                ..
+            })
+        ));
+    }
+}
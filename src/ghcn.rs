@@ -0,0 +1,169 @@
+use crate::conversions::{eto_to_etr, ETO_ETR_RATIO};
+use crate::{calculate_hargreaves_eto, EaInput, RefEtError, Station};
+use chrono::{Datelike, NaiveDate};
+
+/// Value GHCN-Daily uses for a missing observation.
+const GHCN_MISSING: i32 = -9999;
+
+/// One station-day of GHCN-Daily elements, in the archive's integer units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GhcnDailyRecord {
+    pub date: NaiveDate,   // day of the observations
+    pub tmax: Option<i32>, // TMAX, maximum temperature in tenths of a degree Celsius
+    pub tmin: Option<i32>, // TMIN, minimum temperature in tenths of a degree Celsius
+    pub prcp: Option<i32>, // PRCP, precipitation in tenths of a mm
+    pub awnd: Option<i32>, // AWND, average daily wind speed in tenths of m/s
+}
+
+impl GhcnDailyRecord {
+    /// Reads one station-day from GHCN-Daily by-year CSV rows, one element per row, such as
+    /// `USC00053553,20000701,TMAX,324,,,7,0700`.
+    ///
+    /// Elements other than TMAX, TMIN, PRCP, and AWND are ignored, as are missing values of -9999
+    /// and values with a quality flag, which failed a GHCN quality check.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The CSV rows of the day, one per line.
+    ///
+    /// # Returns
+    ///
+    /// * The record, or `DomainError` when a row is malformed or the rows are for different days.
+    pub fn from_csv(rows: &str) -> Result<GhcnDailyRecord, RefEtError> {
+        let mut record: Option<GhcnDailyRecord> = None;
+
+        for row in rows.lines().filter(|row| !row.trim().is_empty()) {
+            let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+            if fields.len() < 4 {
+                return Err(RefEtError::DomainError("ghcn row has too few fields"));
+            }
+
+            let date = NaiveDate::parse_from_str(fields[1], "%Y%m%d")
+                .map_err(|_| RefEtError::DomainError("ghcn date is not YYYYMMDD"))?;
+            let record = record.get_or_insert(GhcnDailyRecord {
+                date,
+                tmax: None,
+                tmin: None,
+                prcp: None,
+                awnd: None,
+            });
+            if record.date != date {
+                return Err(RefEtError::DomainError("ghcn rows are for different days"));
+            }
+
+            let value: i32 = fields[3]
+                .parse()
+                .map_err(|_| RefEtError::DomainError("ghcn value is not an integer"))?;
+            let quality_flag = fields.get(5).copied().unwrap_or("");
+            if value == GHCN_MISSING || !quality_flag.is_empty() {
+                continue;
+            }
+
+            match fields[2] {
+                "TMAX" => record.tmax = Some(value),
+                "TMIN" => record.tmin = Some(value),
+                "PRCP" => record.prcp = Some(value),
+                "AWND" => record.awnd = Some(value),
+                _ => {}
+            }
+        }
+
+        record.ok_or(RefEtError::DomainError("ghcn rows are empty"))
+    }
+}
+
+/// Calculates the short and tall reference et for a GHCN-Daily station-day.
+///
+/// GHCN-Daily has no humidity or solar radiation, so ea is estimated from Tmin as the dewpoint and
+/// Rs with Hargreaves-Samani. With AWND the ASCE Standardized equation is used, taking the wind as
+/// measured at the station's wind height. When only TMAX and TMIN exist the short reference is the
+/// Hargreaves ETo and the tall reference is scaled from it with `ETO_ETR_RATIO`.
+///
+/// # Arguments
+///
+/// * `record` - The station-day of GHCN elements.
+/// * `station` - The station the record is from.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration, or `MissingField` when
+///   TMAX or TMIN is missing.
+pub fn et_from_ghcn(record: &GhcnDailyRecord, station: &Station) -> Result<(f64, f64), RefEtError> {
+    let tmax = f64::from(record.tmax.ok_or(RefEtError::MissingField("tmax"))?) / 10.0;
+    let tmin = f64::from(record.tmin.ok_or(RefEtError::MissingField("tmin"))?) / 10.0;
+
+    match record.awnd {
+        Some(awnd) => station.ref_et(
+            &record.date.format("%Y-%m-%d").to_string(),
+            tmax,
+            tmin,
+            EaInput::builder().tmin(tmin, "C").build()?,
+            None,
+            f64::from(awnd) / 10.0,
+        ),
+        None => {
+            let eto =
+                calculate_hargreaves_eto(tmax, tmin, station.latitude_rad(), record.date.ordinal());
+            Ok((eto, eto_to_etr(eto, ETO_ETR_RATIO)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn greeley() -> Station {
+        Station::new(40.41_f64.to_radians(), 1462.4, 2.0).unwrap()
+    }
+
+    #[test]
+    fn test_ghcn_record_from_csv() {
+        // Given, a synthetic by-year GHCN day with a failed SNOW value and a missing WSF2
+        let rows = "USC00053553,20000701,TMAX,324,,,7,0700\n\
+                    USC00053553,20000701,TMIN,109,,,7,0700\n\
+                    USC00053553,20000701,PRCP,0,,,7,0700\n\
+                    USC00053553,20000701,SNOW,5,,I,7,0700\n\
+                    USC00053553,20000701,AWND,19,,,W,\n\
+                    USC00053553,20000701,WSF2,-9999,,,W,\n";
+
+        // When
+        let record = GhcnDailyRecord::from_csv(rows).unwrap();
+        let (short, tall) = et_from_ghcn(&record, &greeley()).unwrap();
+
+        assert_eq!(record.date, NaiveDate::from_ymd_opt(2000, 7, 1).unwrap());
+        assert_eq!(record.tmax, Some(324));
+        assert_eq!(record.tmin, Some(109));
+        assert_eq!(record.awnd, Some(19));
+        assert!(short > 0.0 && tall > short);
+    }
+
+    #[test]
+    fn test_et_from_ghcn_temperature_only() {
+        // Given
+        let record = GhcnDailyRecord {
+            date: NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+            tmax: Some(324),
+            tmin: Some(109),
+            prcp: None,
+            awnd: None,
+        };
+
+        // When
+        let (short, tall) = et_from_ghcn(&record, &greeley()).unwrap();
+
+        // the Hargreaves ETo of the Greeley day
+        assert!((short - 7.15).abs() < 0.01);
+        assert!((tall - short / ETO_ETR_RATIO).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_et_from_ghcn_missing_tmin() {
+        let record = GhcnDailyRecord::from_csv("USC00053553,20000701,TMAX,324,,,7,0700").unwrap();
+
+        assert_eq!(
+            et_from_ghcn(&record, &greeley()),
+            Err(RefEtError::MissingField("tmin"))
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use crate::et::{
+    calc_rn, calc_rns_with_albedo, calc_rso, calc_ws, eo, es_slope, inverse_rel_dist_factor,
+    mean_temp, psy_constant, solar_declin, sunset_hour_angle, DailyValues,
+};
+use crate::{RefEtConfig, RefEtError};
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use climate::output::Output;
+use std::f64::consts::PI;
+
+/// Stefan-Boltzmann constant in MJ/K4/m2/hour.
+const SIGMA_HOURLY: f64 = 2.042e-10;
+
+/// Rs/Rso assumed at night, when there is no sun to measure cloudiness against. ASCE carries the
+/// ratio from 2 to 3 hours before sunset, which a single hour can't see, so the FAO-56 guidance for
+/// semi-arid sites is used instead.
+const NIGHT_RELATIVE_RS: f64 = 0.7;
+
+/// Hourly constants for one reference surface as (Cn, Cd day, Cd night, G/Rn day, G/Rn night).
+const SHORT_HOURLY: (f64, f64, f64, f64, f64) = (37.0, 0.24, 0.96, 0.1, 0.5);
+const TALL_HOURLY: (f64, f64, f64, f64, f64) = (66.0, 0.25, 1.7, 0.04, 0.2);
+
+/// Calculates the short and tall reference et for a one hour period.
+///
+/// The output carries the values for the hour: tmax and tmin are averaged into the hourly air
+/// temperature (pass the same value for both), Rs is in MJ/m2/hour, and the humidity values are
+/// used with the same ea method selection as the daily calculation. The station is assumed to sit
+/// on the meridian of its time zone, so solar time is the clock time plus the seasonal correction.
+/// Use `calculate_ref_et_hourly_utc` for a station away from its time zone meridian.
+///
+/// # Arguments
+///
+/// * `input` - The output values for the hour, which must include measured solar radiation.
+/// * `clock_hour` - Standard clock time at the midpoint of the period, e.g. 14.5 for 14:00-15:00.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration in mm/hour, or an error
+///   when Rs or wind speed is missing, ea can't be calculated from the humidity inputs, or
+///   `clock_hour` is outside 0 to 24.
+pub fn calculate_ref_et_hourly(input: &Output, clock_hour: f64) -> Result<(f64, f64), RefEtError> {
+    if !(0.0..=24.0).contains(&clock_hour) {
+        return Err(RefEtError::DomainError(
+            "clock hour must be between 0 and 24",
+        ));
+    }
+
+    let values = DailyValues::from_output(input, &RefEtConfig::default())?;
+    let solar_hour = (clock_hour + seasonal_correction(values.doy)).rem_euclid(24.0);
+    ref_et_hourly(&values, values.doy, solar_hour)
+}
+
+/// Calculates the short and tall reference et for a one hour period like
+/// `calculate_ref_et_hourly`, placing the sun from the UTC instant and the station longitude.
+///
+/// The sun's position comes from `solar_time`, so no time zone or daylight saving offset is
+/// involved.
+///
+/// # Arguments
+///
+/// * `input` - The output values for the hour, which must include measured solar radiation.
+/// * `datetime` - The midpoint of the period in UTC, e.g. 21:30 UTC for 14:00-15:00 MST.
+/// * `longitude_deg` - Longitude of the station in degrees, positive east of Greenwich.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration in mm/hour, or an error
+///   when Rs or wind speed is missing or ea can't be calculated from the humidity inputs.
+pub fn calculate_ref_et_hourly_utc(
+    input: &Output,
+    datetime: &DateTime<Utc>,
+    longitude_deg: f64,
+) -> Result<(f64, f64), RefEtError> {
+    let values = DailyValues::from_output(input, &RefEtConfig::default())?;
+    ref_et_hourly(
+        &values,
+        datetime.ordinal(),
+        solar_time(datetime, longitude_deg),
+    )
+}
+
+fn ref_et_hourly(
+    values: &DailyValues,
+    doy: u32,
+    solar_hour: f64,
+) -> Result<(f64, f64), RefEtError> {
+    let config = RefEtConfig::default();
+    let rs = values.rs.ok_or(RefEtError::MissingField("rs"))?;
+
+    let temperature = mean_temp(values.tmax, values.tmin);
+    let gamma = psy_constant(values.pressure());
+    let delta = es_slope(temperature);
+    let vpd = eo(temperature) - values.ea;
+
+    let ra = calc_ra_hourly(values.latitude, doy, solar_hour);
+    let rso = calc_rso(ra, values.z);
+    let relative_rs = if rso > 0.0 {
+        (rs / rso).clamp(0.3, 1.0)
+    } else {
+        NIGHT_RELATIVE_RS
+    };
+    let fcd = 1.35 * relative_rs - 0.35; // Eq. 45
+
+    // net long-wave radiation for the hour, Eq. 44
+    let rnl =
+        SIGMA_HOURLY * fcd * (0.34 - 0.14 * values.ea.sqrt()) * (temperature + 273.16).powi(4);
+    let rn = calc_rn(calc_rns_with_albedo(rs, config.albedo), rnl);
+
+    let u2 = calc_ws(values.ws, values.wz);
+    let is_day = rn > 0.0;
+    let et = |(cn, cd_day, cd_night, g_day, g_night): (f64, f64, f64, f64, f64)| {
+        let cd = if is_day { cd_day } else { cd_night };
+        let g = hourly_g(rn, is_day, g_day, g_night);
+        (0.408 * delta * (rn - g) + gamma * (cn / (temperature + 273.0)) * u2 * vpd)
+            / (delta + gamma * (1.0 + cd * u2))
+    };
+
+    Ok((et(SHORT_HOURLY), et(TALL_HOURLY)))
+}
+
+/// Calculates the hourly soil heat flux density of the short reference, 0.1 * Rn in daytime and
+/// 0.5 * Rn at night.
+///
+/// G is positive when heat flows down into the soil. At night Rn is negative, so G is negative and
+/// the soil gives heat back to the surface, which adds to the energy available for ET in Rn - G.
+///
+/// # Arguments
+///
+/// * `rn` - Net radiation for the hour in MJ/m2/hour.
+/// * `is_daytime` - Whether the hour is in daytime, when Rn is positive.
+///
+/// # Returns
+///
+/// * The soil heat flux density in MJ/m2/hour.
+pub fn soil_heat_flux(rn: f64, is_daytime: bool) -> f64 {
+    let (_, _, _, g_day, g_night) = SHORT_HOURLY;
+    hourly_g(rn, is_daytime, g_day, g_night)
+}
+
+fn hourly_g(rn: f64, is_daytime: bool, g_day: f64, g_night: f64) -> f64 {
+    if is_daytime {
+        g_day * rn
+    } else {
+        g_night * rn
+    }
+}
+
+/// Calculates the Extraterrestrial Radiation for a one hour period. Found in equation 48.
+///
+/// # Arguments
+///
+/// * `latitude` - Latitude in radians.
+/// * `doy` - Day of the year.
+/// * `solar_hour` - Solar time at the midpoint of the period, from `solar_time`.
+///
+/// # Returns
+///
+/// * The Extraterrestrial Radiation for the hour in MJ/m2/hour, zero when the sun is down.
+pub(crate) fn calc_ra_hourly(latitude: f64, doy: u32, solar_hour: f64) -> f64 {
+    let dr = inverse_rel_dist_factor(doy);
+    let delta = solar_declin(doy);
+    let omega_s = sunset_hour_angle(latitude, delta);
+
+    let omega = PI / 12.0 * (solar_hour - 12.0); // Eq. 55
+    let omega_2 = (omega + PI / 24.0).clamp(-omega_s, omega_s); // Eq. 54
+    let omega_1 = (omega - PI / 24.0).clamp(-omega_s, omega_2); // Eq. 53
+
+    12.0 / PI
+        * 4.92
+        * dr
+        * ((omega_2 - omega_1) * latitude.sin() * delta.sin()
+            + latitude.cos() * delta.cos() * (omega_2.sin() - omega_1.sin()))
+    // Eq. 48
+}
+
+/// Calculates the Extraterrestrial Radiation received between two times of day by integrating
+/// between their hour angles (Eq. 48), limited to sunrise and sunset.
+///
+/// # Arguments
+///
+/// * `latitude_rad` - Latitude in radians.
+/// * `doy` - Day of the year.
+/// * `start_hour` - Start of the period in solar time hours, 0 to 24.
+/// * `end_hour` - End of the period in solar time hours, after `start_hour` and at most 24.
+///
+/// # Returns
+///
+/// * The Extraterrestrial Radiation for the period in MJ/m2, where 0 to 24 matches the daily Ra,
+///   or `DomainError` when the period is empty or outside the day.
+pub fn calc_ra_period(
+    latitude_rad: f64,
+    doy: u32,
+    start_hour: f64,
+    end_hour: f64,
+) -> Result<f64, RefEtError> {
+    if !(0.0..=24.0).contains(&start_hour) || !(0.0..=24.0).contains(&end_hour) {
+        return Err(RefEtError::DomainError(
+            "period hours must be between 0 and 24",
+        ));
+    }
+    if start_hour >= end_hour {
+        return Err(RefEtError::DomainError(
+            "period start must be before its end",
+        ));
+    }
+
+    let dr = inverse_rel_dist_factor(doy);
+    let delta = solar_declin(doy);
+    let omega_s = sunset_hour_angle(latitude_rad, delta);
+
+    let omega_1 = (PI / 12.0 * (start_hour - 12.0)).clamp(-omega_s, omega_s);
+    let omega_2 = (PI / 12.0 * (end_hour - 12.0)).clamp(-omega_s, omega_s);
+
+    Ok(12.0 / PI
+        * 4.92
+        * dr
+        * ((omega_2 - omega_1) * latitude_rad.sin() * delta.sin()
+            + latitude_rad.cos() * delta.cos() * (omega_2.sin() - omega_1.sin())))
+    // Eq. 48
+}
+
+/// Calculates the seasonal correction for solar time. Found in equation 57.
+///
+/// # Arguments
+///
+/// * `doy` - Day of the year.
+///
+/// # Returns
+///
+/// * The seasonal correction in hours.
+pub(crate) fn seasonal_correction(doy: u32) -> f64 {
+    let b = 2.0 * PI * (doy as f64 - 81.0) / 364.0; // Eq. 58
+    0.1645 * (2.0 * b).sin() - 0.1255 * b.cos() - 0.025 * b.sin() // Eq. 57
+}
+
+/// Calculates the apparent solar time for an instant in UTC.
+///
+/// Both the fractional hour and the day of year used for the seasonal correction are taken from
+/// the UTC instant, so the result is continuous across daylight saving transitions and never
+/// assumes a local day of exactly 86400 seconds. Convert to local time only for labeling results.
+///
+/// # Arguments
+///
+/// * `datetime` - The instant in UTC, usually the midpoint of the hourly period.
+/// * `longitude_deg` - Longitude of the station in degrees, positive east of Greenwich.
+///
+/// # Returns
+///
+/// * The solar time in hours in the range [0, 24).
+pub fn solar_time(datetime: &DateTime<Utc>, longitude_deg: f64) -> f64 {
+    let utc_hours = datetime.hour() as f64
+        + datetime.minute() as f64 / 60.0
+        + datetime.second() as f64 / 3600.0
+        + datetime.nanosecond() as f64 / 3.6e12;
+
+    (utc_hours + longitude_deg / 15.0 + seasonal_correction(datetime.ordinal())).rem_euclid(24.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::et::calc_ra;
+    use chrono::{NaiveDate, TimeZone};
+
+    #[test]
+    fn test_soil_heat_flux_day_and_night() {
+        // Given
+        let day_rn = 2.1;
+        let night_rn = -0.2;
+
+        // When
+        let day_g = soil_heat_flux(day_rn, true);
+        let night_g = soil_heat_flux(night_rn, false);
+
+        assert!((day_g - 0.1 * day_rn).abs() < 1e-12);
+        assert!((night_g - 0.5 * night_rn).abs() < 1e-12);
+        assert!(night_g < 0.0);
+    }
+
+    #[test]
+    fn test_calc_ra_period_full_day() {
+        // Given
+        let latitude = 40.41_f64.to_radians();
+
+        // When
+        let ra = calc_ra_period(latitude, 183, 0.0, 24.0).unwrap();
+
+        assert!((ra - calc_ra(latitude, 183)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_calc_ra_period_halves() {
+        let latitude = -33.87_f64.to_radians();
+
+        let morning = calc_ra_period(latitude, 15, 0.0, 12.0).unwrap();
+        let daylight = calc_ra_period(latitude, 15, 6.0, 18.0).unwrap();
+
+        assert!((2.0 * morning - calc_ra(latitude, 15)).abs() < 1e-9);
+        assert!(daylight < calc_ra(latitude, 15));
+        assert!(calc_ra_period(latitude, 15, 18.0, 6.0).is_err());
+        assert!(calc_ra_period(latitude, 15, 6.0, 25.0).is_err());
+    }
+
+    fn greeley_hour(temperature: f64, rs: f64) -> Output {
+        Output::new_with_values(
+            temperature,
+            temperature,
+            None,
+            None,
+            None,
+            Some(1.1),
+            Some(rs),
+            Some(2.0),
+            Some(2.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_hourly_ra_sums_to_daily() {
+        // Given
+        let latitude = 40.41_f64.to_radians();
+
+        // When
+        let total: f64 = (0..24)
+            .map(|h| calc_ra_hourly(latitude, 183, h as f64 + 0.5))
+            .sum();
+
+        assert!((total - calc_ra(latitude, 183)).abs() < 1e-9);
+    }
+
+    /// Longitude of the Mountain Standard Time meridian, where solar time is clock time plus the
+    /// seasonal correction.
+    const MST_MERIDIAN: f64 = -105.0;
+
+    #[test]
+    fn test_ref_et_hourly_afternoon() {
+        // Given, 14:00-15:00 MST
+        let hour = greeley_hour(30.9, 3.0);
+        let midpoint = Utc.with_ymd_and_hms(2000, 7, 1, 21, 30, 0).unwrap();
+
+        // When
+        let (short, tall) = calculate_ref_et_hourly_utc(&hour, &midpoint, MST_MERIDIAN).unwrap();
+
+        assert!((short - 0.683).abs() < 0.001);
+        assert!((tall - 0.824).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_ref_et_hourly_clock_hour_on_meridian() {
+        // Given, a station on the MST meridian where clock and UTC time place the sun the same
+        let afternoon = greeley_hour(30.9, 3.0);
+        let night = greeley_hour(20.0, 0.0);
+
+        // When
+        let (short, tall) = calculate_ref_et_hourly(&afternoon, 14.5).unwrap();
+        let (night_short, night_tall) = calculate_ref_et_hourly(&night, 2.5).unwrap();
+
+        let utc = |hour, minute| Utc.with_ymd_and_hms(2000, 7, 1, hour, minute, 0).unwrap();
+        let expected = calculate_ref_et_hourly_utc(&afternoon, &utc(21, 30), MST_MERIDIAN).unwrap();
+        let expected_night =
+            calculate_ref_et_hourly_utc(&night, &utc(9, 30), MST_MERIDIAN).unwrap();
+        assert!((short - expected.0).abs() < 1e-9);
+        assert!((tall - expected.1).abs() < 1e-9);
+        assert!((night_short - expected_night.0).abs() < 1e-9);
+        assert!((night_tall - expected_night.1).abs() < 1e-9);
+        assert!(calculate_ref_et_hourly(&afternoon, 25.0).is_err());
+    }
+
+    #[test]
+    fn test_ref_et_hourly_night() {
+        // Given, 02:00-03:00 MST
+        let hour = greeley_hour(20.0, 0.0);
+        let midpoint = Utc.with_ymd_and_hms(2000, 7, 1, 9, 30, 0).unwrap();
+
+        // When
+        let (short, tall) = calculate_ref_et_hourly_utc(&hour, &midpoint, MST_MERIDIAN).unwrap();
+
+        assert!((short - 0.041).abs() < 0.001);
+        assert!((tall - 0.059).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_seasonal_correction() {
+        // Given
+        let doy = 183;
+
+        // When
+        let sc = seasonal_correction(doy);
+
+        // seasonal correction is about -4 minutes in early July
+        assert!((sc - -0.0618).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_solar_time_continuous_across_spring_forward() {
+        // Given, Greeley clocks jump from 02:00 MST to 03:00 MDT at 09:00 UTC on 2024-03-10
+        let longitude = -104.78;
+        let hours: Vec<f64> = (6..13)
+            .map(|h| {
+                let instant = Utc.with_ymd_and_hms(2024, 3, 10, h, 30, 0).unwrap();
+                solar_time(&instant, longitude)
+            })
+            .collect();
+
+        // When / Then every UTC hour advances solar time by exactly one hour, wrapping at solar
+        // midnight
+        for pair in hours.windows(2) {
+            assert!(((pair[1] - pair[0]).rem_euclid(24.0) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_solar_time_noon() {
+        // Given, 12:00 UTC on the prime meridian
+        let instant = Utc.with_ymd_and_hms(2000, 7, 1, 12, 0, 0).unwrap();
+
+        // When
+        let solar = solar_time(&instant, 0.0);
+
+        assert!((solar - (12.0 + seasonal_correction(183))).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,1039 @@
+use crate::conversions::{
+    feet_to_meters, lang_to_mj, mph_to_mps, pa_to_kpa, parse_latitude_dms, watts_to_mj,
+    wind_run_to_speed,
+};
+use crate::et::{check_cloud_cover, check_rs, ref_et_from_values, to_celsius, DailyValues};
+use crate::{RefEtConfig, RefEtError};
+use chrono::NaiveDate;
+use climate::output::Output;
+use std::f64::consts::FRAC_PI_2;
+
+/// A measurement along with the units it was recorded in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Value {
+    value: f64,    // the measured value
+    units: String, // unit abbreviation such as "C", "F", "kPa", or "Pa"
+}
+
+impl Value {
+    pub fn new(value: f64, units: &str) -> Value {
+        Value {
+            value,
+            units: units.to_string(),
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn units(&self) -> &str {
+        &self.units
+    }
+
+    fn celsius(&self) -> Result<f64, RefEtError> {
+        to_celsius(self.value, &self.units)
+    }
+
+    fn kilopascals(&self) -> Result<f64, RefEtError> {
+        self.to("kPa")
+    }
+
+    fn megajoules(&self) -> Result<f64, RefEtError> {
+        self.to("MJ/m2/day")
+    }
+
+    /// Converts the value to other units of the same dimension, such as "F" to "C" or "mph" to
+    /// "m/s".
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Units to convert to.
+    ///
+    /// # Returns
+    ///
+    /// * The value in the target units, or `InvalidUnits` when either units aren't recognized or
+    ///   they measure different dimensions, such as "m" and "kPa".
+    pub fn to(&self, target: &str) -> Result<f64, RefEtError> {
+        let (dimension, base) = to_base_units(self.value, &self.units)
+            .ok_or_else(|| RefEtError::InvalidUnits(self.units.clone()))?;
+
+        // every supported conversion is linear, so the inverse comes from two points
+        let (target_dimension, offset) = to_base_units(0.0, target)
+            .ok_or_else(|| RefEtError::InvalidUnits(target.to_string()))?;
+        if target_dimension != dimension {
+            return Err(RefEtError::InvalidUnits(format!(
+                "{} to {}",
+                self.units, target
+            )));
+        }
+        let (_, one) = to_base_units(1.0, target)
+            .ok_or_else(|| RefEtError::InvalidUnits(target.to_string()))?;
+
+        Ok((base - offset) / (one - offset))
+    }
+}
+
+/// Physical quantity measured by a unit, only units of the same dimension convert to each other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dimension {
+    Temperature,
+    Speed,
+    Length,
+    Radiation,
+    Pressure,
+}
+
+/// Converts a value to the base units of its dimension: Celsius, m/s, meters, MJ/m2/day, or kPa.
+fn to_base_units(value: f64, units: &str) -> Option<(Dimension, f64)> {
+    if let Ok(celsius) = to_celsius(value, units) {
+        return Some((Dimension::Temperature, celsius));
+    }
+
+    let converted = match units.trim().to_lowercase().as_str() {
+        "m/s" | "mps" => (Dimension::Speed, value),
+        "mph" => (Dimension::Speed, mph_to_mps(value)),
+        "km/h" | "kph" => (Dimension::Speed, value / 3.6),
+        "m" | "meters" => (Dimension::Length, value),
+        "ft" | "feet" => (Dimension::Length, feet_to_meters(value)),
+        "mj/m2/day" | "mj" => (Dimension::Radiation, value),
+        "w/m2" => (Dimension::Radiation, watts_to_mj(value)),
+        "langleys" | "langley" | "ly" => (Dimension::Radiation, lang_to_mj(value)),
+        "kpa" => (Dimension::Pressure, value),
+        "pa" => (Dimension::Pressure, pa_to_kpa(value)),
+        "hpa" | "mb" => (Dimension::Pressure, value / 10.0),
+        _ => return None,
+    };
+
+    Some(converted)
+}
+
+/// Daily weather and site values for a reference ET calculation, built up with setters.
+///
+/// Values are converted to the units of the ASCE Standardized equation when they are set, so a
+/// bad unit or an impossible value is reported by the setter rather than by the calculation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    tmax: f64,                   // daily maximum air temperature in Celsius
+    tmin: f64,                   // daily minimum air temperature in Celsius
+    ea: Option<f64>,             // actual vapor pressure in kPa
+    dewpoint: Option<f64>,       // dewpoint temperature in Celsius
+    rhmax: Option<f64>,          // daily maximum relative humidity in %
+    rhmin: Option<f64>,          // daily minimum relative humidity in %
+    rs: Option<f64>,             // solar radiation in MJ/m2/day
+    rn: Option<f64>,             // measured net radiation in MJ/m2/day
+    sunshine_hours: Option<f64>, // hours of bright sunshine
+    cloud_cover: Option<f64>,    // cloud cover as a fraction, oktas, or a percentage
+    ws: Option<f64>,             // wind speed in m/s measured at wz
+    wz: Option<f64>,             // wind measurement height in meters
+    z: Option<f64>,              // station elevation in meters
+    pressure: Option<f64>,       // measured atmospheric pressure in kPa
+    latitude: Option<f64>,       // station latitude in radians
+    g: Option<f64>,              // measured soil heat flux density in MJ/m2/day
+    date: NaiveDate,             // date of the record
+}
+
+impl Input {
+    /// Creates an input from the daily temperatures, with every other value missing until it is
+    /// set.
+    ///
+    /// # Arguments
+    ///
+    /// * `tmax` - Daily maximum air temperature in "C", "F", or "K".
+    /// * `tmin` - Daily minimum air temperature in "C", "F", or "K".
+    /// * `date` - Date of the record.
+    ///
+    /// # Returns
+    ///
+    /// * The input, or an error when the units are not temperatures or tmin exceeds tmax once
+    ///   both are in Celsius.
+    pub fn new(tmax: Value, tmin: Value, date: NaiveDate) -> Result<Input, RefEtError> {
+        Input::new_with_tolerance(tmax, tmin, date, 0.0)
+    }
+
+    /// Creates an input like `new`, allowing tmin to exceed tmax by up to `tolerance` degrees
+    /// Celsius to absorb rounding in the recorded units. Within the tolerance tmin is set to tmax.
+    ///
+    /// The check runs after both temperatures are converted to Celsius, so a pair recorded in
+    /// different units that inverts on conversion is rejected.
+    ///
+    /// # Returns
+    ///
+    /// * The input, `InvalidUnits` when the units are not temperatures, or `OutOfRange` for tmin
+    ///   when it exceeds tmax by more than the tolerance.
+    pub fn new_with_tolerance(
+        tmax: Value,
+        tmin: Value,
+        date: NaiveDate,
+        tolerance: f64,
+    ) -> Result<Input, RefEtError> {
+        if tolerance.is_nan() || tolerance < 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "tolerance",
+                value: tolerance,
+            });
+        }
+
+        let tmax = tmax.celsius()?;
+        let tmin = tmin.celsius()?;
+        if tmin > tmax + tolerance {
+            return Err(RefEtError::OutOfRange {
+                field: "tmin",
+                value: tmin,
+            });
+        }
+        let tmin = tmin.min(tmax);
+
+        Ok(Input {
+            tmax,
+            tmin,
+            ea: None,
+            dewpoint: None,
+            rhmax: None,
+            rhmin: None,
+            rs: None,
+            rn: None,
+            sunshine_hours: None,
+            cloud_cover: None,
+            ws: None,
+            wz: None,
+            z: None,
+            pressure: None,
+            latitude: None,
+            g: None,
+            date,
+        })
+    }
+
+    /// Creates an input from daily temperatures in Fahrenheit, for records kept in US customary
+    /// units. The imperial setters convert the remaining values as they are set.
+    ///
+    /// # Arguments
+    ///
+    /// * `tmax_f` - Daily maximum air temperature in Fahrenheit.
+    /// * `tmin_f` - Daily minimum air temperature in Fahrenheit.
+    /// * `date` - Date of the record in the format "YYYY-MM-DD".
+    ///
+    /// # Returns
+    ///
+    /// * The input, or an error when the date is malformed or tmin exceeds tmax.
+    pub fn new_imperial(tmax_f: f64, tmin_f: f64, date: &str) -> Result<Input, RefEtError> {
+        let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+            RefEtError::DomainError("date must be a valid date in the format YYYY-MM-DD")
+        })?;
+
+        Input::new(Value::new(tmax_f, "F"), Value::new(tmin_f, "F"), date)
+    }
+
+    /// Sets the actual vapor pressure in "kPa" or "Pa".
+    pub fn set_ea(&mut self, ea: Value) -> Result<&mut Input, RefEtError> {
+        let ea = ea.kilopascals()?;
+        if ea < 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "ea",
+                value: ea,
+            });
+        }
+        self.ea = Some(ea);
+        Ok(self)
+    }
+
+    /// Sets the dewpoint temperature in "C" or "F".
+    pub fn set_dewpoint(&mut self, dewpoint: Value) -> Result<&mut Input, RefEtError> {
+        self.dewpoint = Some(dewpoint.celsius()?);
+        Ok(self)
+    }
+
+    /// Sets the daily maximum and minimum relative humidity in percent.
+    pub fn set_rh(&mut self, rhmax: f64, rhmin: f64) -> Result<&mut Input, RefEtError> {
+        for (field, value) in [("rhmax", rhmax), ("rhmin", rhmin)] {
+            if !(0.0..=100.0).contains(&value) {
+                return Err(RefEtError::OutOfRange { field, value });
+            }
+        }
+        if rhmin > rhmax {
+            return Err(RefEtError::DomainError("rhmin must not exceed rhmax"));
+        }
+        self.rhmax = Some(rhmax);
+        self.rhmin = Some(rhmin);
+        Ok(self)
+    }
+
+    /// Sets only the daily maximum relative humidity in percent, for records without RHmin.
+    pub fn set_rhmax(&mut self, rhmax: f64) -> Result<&mut Input, RefEtError> {
+        if !(0.0..=100.0).contains(&rhmax) {
+            return Err(RefEtError::OutOfRange {
+                field: "rhmax",
+                value: rhmax,
+            });
+        }
+        self.rhmax = Some(rhmax);
+        Ok(self)
+    }
+
+    /// Sets only the daily minimum relative humidity in percent, for records without RHmax.
+    pub fn set_rhmin(&mut self, rhmin: f64) -> Result<&mut Input, RefEtError> {
+        if !(0.0..=100.0).contains(&rhmin) {
+            return Err(RefEtError::OutOfRange {
+                field: "rhmin",
+                value: rhmin,
+            });
+        }
+        self.rhmin = Some(rhmin);
+        Ok(self)
+    }
+
+    /// Sets the measured solar radiation in MJ/m2/day, returning `OutOfRange` when it is negative.
+    pub fn set_rs(&mut self, rs: f64) -> Result<&mut Input, RefEtError> {
+        check_rs(Some(rs))?;
+        self.rs = Some(rs);
+        Ok(self)
+    }
+
+    /// Sets the net radiation in MJ/m2/day measured by a net radiometer, which is used in place
+    /// of the estimate from Rs. Rs is still used for the cloudiness term when it is set.
+    pub fn set_rn(&mut self, rn: f64) -> Result<&mut Input, RefEtError> {
+        self.rn = Some(rn);
+        Ok(self)
+    }
+
+    /// Sets the soil heat flux density measured by heat-flux plates in "MJ/m2/day", "W/m2" as a
+    /// daily mean, or langleys, which is used in place of the daily assumption that G is 0.
+    pub fn set_g(&mut self, g: f64, units: &str) -> Result<&mut Input, RefEtError> {
+        self.g = Some(Value::new(g, units).megajoules()?);
+        Ok(self)
+    }
+
+    /// Sets the hours of bright sunshine, used to estimate Rs when it isn't measured.
+    pub fn set_sunshine_hours(&mut self, hours: f64) -> Result<&mut Input, RefEtError> {
+        if !(0.0..=24.0).contains(&hours) {
+            return Err(RefEtError::OutOfRange {
+                field: "sunshine_hours",
+                value: hours,
+            });
+        }
+        self.sunshine_hours = Some(hours);
+        Ok(self)
+    }
+
+    /// Sets the cloud cover, used to estimate Rs when neither Rs nor sunshine hours are set. It is
+    /// read like `calc_rs_from_cloud_cover`: a fraction up to 1, oktas up to 8, or a percentage.
+    pub fn set_cloud_cover(&mut self, cloud_cover: f64) -> Result<&mut Input, RefEtError> {
+        check_cloud_cover(cloud_cover)?;
+        self.cloud_cover = Some(cloud_cover);
+        Ok(self)
+    }
+
+    /// Sets the wind speed in m/s and the height in meters it was measured at.
+    pub fn set_ws(&mut self, ws: f64, wz: f64) -> Result<&mut Input, RefEtError> {
+        if ws < 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "ws",
+                value: ws,
+            });
+        }
+        self.set_wz(wz)?;
+        self.ws = Some(ws);
+        Ok(self)
+    }
+
+    /// Sets the height in meters the wind speed is measured at.
+    pub fn set_wz(&mut self, wz: f64) -> Result<&mut Input, RefEtError> {
+        if wz.is_nan() || wz <= 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "wz",
+                value: wz,
+            });
+        }
+        self.wz = Some(wz);
+        Ok(self)
+    }
+
+    /// Sets the wind speed in miles per hour and the height in feet it was measured at.
+    pub fn set_ws_mph(&mut self, ws_mph: f64, wz_feet: f64) -> Result<&mut Input, RefEtError> {
+        self.set_ws(mph_to_mps(ws_mph), feet_to_meters(wz_feet))
+    }
+
+    /// Sets the wind speed from a daily wind run in "km/day" or "mi/day". The height it was
+    /// measured at is set separately with `set_wz`.
+    pub fn set_wind_run(&mut self, run: f64, units: &str) -> Result<&mut Input, RefEtError> {
+        let ws = wind_run_to_speed(run, units, 24.0)?;
+        if ws < 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "ws",
+                value: ws,
+            });
+        }
+        self.ws = Some(ws);
+        Ok(self)
+    }
+
+    /// Sets the station elevation in feet.
+    pub fn set_z_feet(&mut self, z_feet: f64) -> Result<&mut Input, RefEtError> {
+        self.set_z(feet_to_meters(z_feet))
+    }
+
+    /// Sets the solar radiation in langleys per day.
+    pub fn set_rs_langleys(&mut self, rs_langleys: f64) -> Result<&mut Input, RefEtError> {
+        self.set_rs(lang_to_mj(rs_langleys))
+    }
+
+    /// Sets the station elevation in meters.
+    ///
+    /// Elevations below the Dead Sea shore or above 9000 m are rejected with `OutOfRange`, which
+    /// catches feet entered as meters at high stations and stray digits. Use `set_z_feet` for
+    /// elevations in feet, which converts before this check.
+    pub fn set_z(&mut self, z: f64) -> Result<&mut Input, RefEtError> {
+        check_elevation(z)?;
+        self.z = Some(z);
+        Ok(self)
+    }
+
+    /// Sets the atmospheric pressure measured by a barometer in "kPa" or "Pa", which is used in
+    /// place of the estimate from elevation.
+    pub fn set_pressure(&mut self, pressure: f64, units: &str) -> Result<&mut Input, RefEtError> {
+        let pressure = Value::new(pressure, units).kilopascals()?;
+        if pressure <= 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "pressure",
+                value: pressure,
+            });
+        }
+        self.pressure = Some(pressure);
+        Ok(self)
+    }
+
+    /// Sets the station latitude in radians.
+    pub fn set_latitude(&mut self, latitude: f64) -> Result<&mut Input, RefEtError> {
+        if latitude.abs() > FRAC_PI_2 {
+            return Err(RefEtError::OutOfRange {
+                field: "latitude",
+                value: latitude,
+            });
+        }
+        self.latitude = Some(latitude);
+        Ok(self)
+    }
+
+    /// Sets the station latitude in "deg" or "rad", checking the range in the units it is given in
+    /// so a value in the wrong units is rejected instead of producing the wrong Ra.
+    ///
+    /// # Returns
+    ///
+    /// * The input, or `OutOfRange` when degrees are beyond ±90 or radians beyond ±π/2, and
+    ///   `InvalidUnits` for any other units.
+    pub fn set_latitude_with_units(
+        &mut self,
+        latitude: f64,
+        units: &str,
+    ) -> Result<&mut Input, RefEtError> {
+        match units.trim().to_lowercase().as_str() {
+            "deg" | "degrees" => {
+                if !(-90.0..=90.0).contains(&latitude) {
+                    return Err(RefEtError::OutOfRange {
+                        field: "latitude",
+                        value: latitude,
+                    });
+                }
+                self.set_latitude(latitude.to_radians())
+            }
+            "rad" | "radians" => self.set_latitude(latitude),
+            _ => Err(RefEtError::InvalidUnits(units.to_string())),
+        }
+    }
+
+    /// Lists the values the ASCE Standardized equation needs that haven't been set, in the order
+    /// `build` reports them: "ea", "ws", "wz", "z", and "latitude". Any humidity that
+    /// `EaInput::new_from_output_with_config` can resolve ea from counts for "ea": ea, dewpoint,
+    /// RHmax, or RHmin.
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        let required = [
+            (
+                "ea",
+                self.ea.is_some()
+                    || self.dewpoint.is_some()
+                    || self.rhmax.is_some()
+                    || self.rhmin.is_some(),
+            ),
+            ("ws", self.ws.is_some()),
+            ("wz", self.wz.is_some()),
+            ("z", self.z.is_some()),
+            ("latitude", self.latitude.is_some()),
+        ];
+
+        required
+            .iter()
+            .filter(|(_, set)| !set)
+            .map(|&(field, _)| field)
+            .collect()
+    }
+
+    /// Whether every value the ASCE Standardized equation needs has been set, so `build` succeeds.
+    pub fn is_complete(&self) -> bool {
+        self.missing_fields().is_empty()
+    }
+
+    /// Checks that everything the ASCE Standardized equation needs has been set.
+    ///
+    /// # Returns
+    ///
+    /// * The validated input, or `MissingField` naming the first of ea (from ea, dewpoint, RHmax,
+    ///   or RHmin), ws, wz, z, or latitude that is missing.
+    pub fn build(&self) -> Result<ValidatedInput, RefEtError> {
+        if let Some(&field) = self.missing_fields().first() {
+            return Err(RefEtError::MissingField(field));
+        }
+
+        let config = RefEtConfig::default();
+        let values = DailyValues {
+            rn: self.rn,
+            sunshine: self.sunshine_hours,
+            cloud: self.cloud_cover,
+            pressure: self.pressure,
+            g: self.g.unwrap_or(0.0),
+            ..DailyValues::from_output(&Output::try_from(self)?, &config)?
+        };
+
+        Ok(ValidatedInput { values })
+    }
+
+    pub fn tmax(&self) -> f64 {
+        self.tmax
+    }
+
+    pub fn tmin(&self) -> f64 {
+        self.tmin
+    }
+
+    pub fn ea(&self) -> Option<f64> {
+        self.ea
+    }
+
+    pub fn rhmax(&self) -> Option<f64> {
+        self.rhmax
+    }
+
+    pub fn rhmin(&self) -> Option<f64> {
+        self.rhmin
+    }
+
+    pub fn rs(&self) -> Option<f64> {
+        self.rs
+    }
+
+    pub fn rn(&self) -> Option<f64> {
+        self.rn
+    }
+
+    pub fn sunshine_hours(&self) -> Option<f64> {
+        self.sunshine_hours
+    }
+
+    pub fn cloud_cover(&self) -> Option<f64> {
+        self.cloud_cover
+    }
+
+    pub fn ws(&self) -> Option<f64> {
+        self.ws
+    }
+
+    pub fn z(&self) -> Option<f64> {
+        self.z
+    }
+
+    /// Sets the station latitude from degrees, minutes, and seconds text such as "40°24'36\"N".
+    pub fn set_latitude_str(&mut self, s: &str) -> Result<&mut Input, RefEtError> {
+        self.set_latitude(parse_latitude_dms(s)?.to_radians())
+    }
+
+    pub fn g(&self) -> Option<f64> {
+        self.g
+    }
+
+    pub fn pressure(&self) -> Option<f64> {
+        self.pressure
+    }
+
+    pub fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+}
+
+/// Range of plausible station elevations in meters, from below the Dead Sea shore at -430 m to
+/// above the highest summits.
+const ELEVATION_RANGE: (f64, f64) = (-500.0, 9000.0);
+
+/// Checks that an elevation in meters is on the surface of the Earth.
+pub(crate) fn check_elevation(z: f64) -> Result<(), RefEtError> {
+    let (lowest, highest) = ELEVATION_RANGE;
+    if !(lowest..=highest).contains(&z) {
+        return Err(RefEtError::OutOfRange {
+            field: "z",
+            value: z,
+        });
+    }
+    Ok(())
+}
+
+/// An `Input` with every value the ET calculation needs, created by `Input::build`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedInput {
+    values: DailyValues, // resolved values in the units of the ASCE Standardized equation
+}
+
+/// Converts the input to an `Output` for the functions that take one, returning `MissingField`
+/// when the elevation or latitude hasn't been set since an `Output` always carries both. Measured
+/// Rn, sunshine hours, pressure, and G have no place in an `Output` and are left behind.
+impl TryFrom<&Input> for Output {
+    type Error = RefEtError;
+
+    fn try_from(input: &Input) -> Result<Output, RefEtError> {
+        let z = input.z.ok_or(RefEtError::MissingField("z"))?;
+        let latitude = input.latitude.ok_or(RefEtError::MissingField("latitude"))?;
+
+        Ok(Output::new_with_values(
+            input.tmax,
+            input.tmin,
+            input.rhmax,
+            input.rhmin,
+            input.dewpoint,
+            input.ea,
+            input.rs,
+            input.ws,
+            input.wz,
+            z,
+            latitude,
+            input.date,
+        ))
+    }
+}
+
+/// Converts an `Output` with temperatures in Celsius to an input, running every value through the
+/// input's setters so an impossible value is reported here.
+impl TryFrom<&Output> for Input {
+    type Error = RefEtError;
+
+    fn try_from(output: &Output) -> Result<Input, RefEtError> {
+        let mut input = Input::new(
+            Value::new(output.get_tmax(), "C"),
+            Value::new(output.get_tmin(), "C"),
+            output.get_date(),
+        )?;
+
+        if let Some(ea) = output.get_ea() {
+            input.set_ea(Value::new(ea, "kPa"))?;
+        }
+        if let Some(dewpoint) = output.get_dewpoint() {
+            input.set_dewpoint(Value::new(dewpoint, "C"))?;
+        }
+        match (output.get_rhmax(), output.get_rhmin()) {
+            (Some(rhmax), Some(rhmin)) => {
+                input.set_rh(rhmax, rhmin)?;
+            }
+            (Some(rhmax), None) => {
+                input.set_rhmax(rhmax)?;
+            }
+            (None, Some(rhmin)) => {
+                input.set_rhmin(rhmin)?;
+            }
+            (None, None) => {}
+        }
+        if let Some(rs) = output.get_rs() {
+            input.set_rs(rs)?;
+        }
+        if let Some(ws) = output.get_ws() {
+            input.set_ws(ws, output.get_wz())?;
+        }
+        input.set_z(output.get_z())?;
+        input.set_latitude(output.get_latitude())?;
+
+        Ok(input)
+    }
+}
+
+/// Calculates the short and tall reference et for an `Input`.
+///
+/// # Arguments
+///
+/// * `input` - The daily weather and site values.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration, or the error from
+///   `Input::build` when a required value is missing.
+pub fn calculate_ref_et_input(input: &Input) -> Result<(f64, f64), RefEtError> {
+    Ok(calculate_ref_et_validated(&input.build()?))
+}
+
+/// Calculates the short and tall reference et for a validated input, which can't fail.
+///
+/// # Arguments
+///
+/// * `input` - The validated daily weather and site values.
+///
+/// # Returns
+///
+/// * a tuple containing the short and tall reference evapotranspiration.
+pub fn calculate_ref_et_validated(input: &ValidatedInput) -> (f64, f64) {
+    ref_et_from_values(&input.values, &RefEtConfig::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_to_compatible_units() {
+        // Given
+        let boiling = Value::new(212.0, "F");
+        let wind = Value::new(10.0, "mph");
+
+        // When
+        let celsius = boiling.to("c").unwrap();
+        let mps = wind.to("m/s").unwrap();
+
+        assert!((celsius - 100.0).abs() < 1e-9);
+        assert!((mps - 4.4704).abs() < 1e-9);
+        assert!((Value::new(100.0, "C").to("K").unwrap() - 373.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_value_to_incompatible_units() {
+        let elevation = Value::new(1462.4, "m");
+
+        assert!(matches!(
+            elevation.to("kpa"),
+            Err(RefEtError::InvalidUnits(_))
+        ));
+        assert!(matches!(
+            elevation.to("furlongs"),
+            Err(RefEtError::InvalidUnits(_))
+        ));
+    }
+
+    #[test]
+    fn test_calculate_ref_et_input_greeley() {
+        // Given
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+        input
+            .set_ea(Value::new(1.27, "kPa"))
+            .and_then(|input| input.set_rs(22.4))
+            .and_then(|input| input.set_ws(1.94, 3.0))
+            .and_then(|input| input.set_z(1462.4))
+            .and_then(|input| input.set_latitude(40.41_f64.to_radians()))
+            .unwrap();
+
+        // When
+        let (short, tall) = calculate_ref_et_input(&input).unwrap();
+
+        assert!((short - 5.69).abs() < 0.01);
+        assert!((tall - 7.32).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_calculate_ref_et_input_measured_rn() {
+        // Given, the net radiation the Greeley case derives from Rs
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+        input
+            .set_ea(Value::new(1.27, "kPa"))
+            .and_then(|input| input.set_rn(13.29))
+            .and_then(|input| input.set_ws(1.94, 3.0))
+            .and_then(|input| input.set_z(1462.4))
+            .and_then(|input| input.set_latitude(40.41_f64.to_radians()))
+            .unwrap();
+
+        // When
+        let (short, tall) = calculate_ref_et_input(&input).unwrap();
+
+        assert!((short - 5.69).abs() < 0.01);
+        assert!((tall - 7.32).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_calculate_ref_et_input_measured_g() {
+        // Given
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+        input
+            .set_ea(Value::new(1.27, "kPa"))
+            .and_then(|input| input.set_rs(22.4))
+            .and_then(|input| input.set_ws(1.94, 3.0))
+            .and_then(|input| input.set_z(1462.4))
+            .and_then(|input| input.set_latitude(40.41_f64.to_radians()))
+            .unwrap();
+        let (short, tall) = calculate_ref_et_input(&input).unwrap();
+
+        // When
+        input.set_g(0.0, "MJ/m2/day").unwrap();
+        let zero = calculate_ref_et_input(&input).unwrap();
+        input.set_g(1.0, "MJ/m2/day").unwrap();
+        let measured = calculate_ref_et_input(&input).unwrap();
+
+        assert_eq!(zero, (short, tall));
+        assert!(measured.0 < short && measured.1 < tall);
+        input.set_g(1.0, "ly").unwrap();
+        assert!((input.g().unwrap() - 0.04184).abs() < 1e-9);
+        assert!(input.set_g(1.0, "kPa").is_err());
+    }
+
+    #[test]
+    fn test_calculate_ref_et_input_imperial_greeley() {
+        // Given, the Greeley case in US customary units
+        let mut input = Input::new_imperial(90.32, 51.62, "2000-07-01").unwrap();
+        input
+            .set_ea(Value::new(1.27, "kPa"))
+            .and_then(|input| input.set_rs_langleys(535.37))
+            .and_then(|input| input.set_ws_mph(4.3396, 9.8425))
+            .and_then(|input| input.set_z_feet(4797.9))
+            .and_then(|input| input.set_latitude(40.41_f64.to_radians()))
+            .unwrap();
+
+        // When
+        let (short, tall) = calculate_ref_et_input(&input).unwrap();
+
+        assert!((short - 5.69).abs() < 0.01);
+        assert!((tall - 7.32).abs() < 0.01);
+        assert!(Input::new_imperial(90.32, 51.62, "2000-7-1x").is_err());
+    }
+
+    #[test]
+    fn test_input_output_round_trip() {
+        // Given
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+        input
+            .set_ea(Value::new(1.27, "kPa"))
+            .and_then(|input| input.set_rs(22.4))
+            .and_then(|input| input.set_ws(1.94, 3.0))
+            .and_then(|input| input.set_z(1462.4))
+            .and_then(|input| input.set_latitude(40.41_f64.to_radians()))
+            .unwrap();
+
+        // When
+        let output = Output::try_from(&input).unwrap();
+        let round_trip = Input::try_from(&output).unwrap();
+
+        let expected = calculate_ref_et_input(&input).unwrap();
+        assert_eq!(
+            crate::calculate_ref_et(&output).unwrap(),
+            crate::RefEt::from(expected)
+        );
+        assert_eq!(round_trip, input);
+        assert_eq!(calculate_ref_et_input(&round_trip).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_rhmin_only_round_trip() {
+        // Given
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            Some(22.0),
+            None,
+            None,
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When
+        let input = Input::try_from(&output).unwrap();
+        let round_trip = Output::try_from(&input).unwrap();
+
+        assert!(input.missing_fields().is_empty());
+        assert_eq!(
+            crate::RefEt::from(calculate_ref_et_input(&input).unwrap()),
+            crate::calculate_ref_et(&output).unwrap()
+        );
+        assert_eq!(round_trip.get_rhmax(), None);
+        assert_eq!(round_trip.get_rhmin(), Some(22.0));
+        assert_eq!(Input::try_from(&round_trip).unwrap(), input);
+    }
+
+    #[test]
+    fn test_output_requires_site_values() {
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+        input.set_z(1462.4).unwrap();
+
+        assert_eq!(
+            Output::try_from(&input).err(),
+            Some(RefEtError::MissingField("latitude"))
+        );
+    }
+
+    #[test]
+    fn test_set_wind_run() {
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+
+        input.set_wind_run(200.0, "km/day").unwrap();
+
+        assert!((input.ws().unwrap() - 2.3148).abs() < 0.0001);
+        assert_eq!(input.missing_fields(), ["ea", "wz", "z", "latitude"]);
+        assert!(input.set_wind_run(200.0, "m/s").is_err());
+    }
+
+    #[test]
+    fn test_set_z_plausible_elevation() {
+        // Given
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+
+        // When
+        let typo = input.set_z(148000.0).map(|_| ());
+        input.set_z_feet(4800.0).unwrap();
+
+        assert_eq!(
+            typo,
+            Err(RefEtError::OutOfRange {
+                field: "z",
+                value: 148000.0
+            })
+        );
+        assert!((input.z().unwrap() - 1463.04).abs() < 0.01);
+        assert!(input.set_z(0.0).is_ok());
+        assert!(input.set_z(-430.0).is_ok());
+        assert!(input.set_z_feet(148000.0).is_err());
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        // Given
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+
+        // When
+        let fresh = input.missing_fields();
+        input
+            .set_dewpoint(Value::new(10.0, "C"))
+            .and_then(|input| input.set_ws(1.94, 3.0))
+            .and_then(|input| input.set_z(1462.4))
+            .and_then(|input| input.set_latitude(40.41_f64.to_radians()))
+            .unwrap();
+
+        assert_eq!(fresh, ["ea", "ws", "wz", "z", "latitude"]);
+        assert!(input.missing_fields().is_empty());
+        assert!(input.is_complete());
+    }
+
+    #[test]
+    fn test_build_reports_missing_fields() {
+        // Given
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(32.4, "C"), Value::new(10.9, "C"), date).unwrap();
+        input
+            .set_ea(Value::new(1.27, "kPa"))
+            .and_then(|input| input.set_z(1462.4))
+            .unwrap();
+
+        // When / Then
+        assert_eq!(input.build(), Err(RefEtError::MissingField("ws")));
+
+        input.set_ws(1.94, 3.0).unwrap();
+        assert_eq!(input.build(), Err(RefEtError::MissingField("latitude")));
+
+        input.set_latitude(40.41_f64.to_radians()).unwrap();
+        input.set_rs(22.4).unwrap();
+        let (short, _) = calculate_ref_et_validated(&input.build().unwrap());
+        assert!((short - 5.69).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_input_converts_fahrenheit() {
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let input = Input::new(Value::new(212.0, "F"), Value::new(32.0, "F"), date).unwrap();
+
+        assert!((input.tmax() - 100.0).abs() < 0.0001);
+        assert!(input.tmin().abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_input_rejects_bad_values() {
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        assert!(Input::new(Value::new(10.0, "C"), Value::new(20.0, "C"), date).is_err());
+
+        let mut input = Input::new(Value::new(30.0, "C"), Value::new(10.0, "C"), date).unwrap();
+        assert!(input.set_ea(Value::new(1.2, "C")).is_err());
+        assert!(input.set_rh(40.0, 80.0).is_err());
+        assert!(input.set_ws(-1.0, 2.0).is_err());
+        assert!(input.set_cloud_cover(150.0).is_err());
+        assert!(input.set_cloud_cover(6.0).is_ok());
+    }
+
+    #[test]
+    fn test_input_equal_temperatures_allowed() {
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+
+        let input = Input::new(Value::new(20.0, "C"), Value::new(68.0, "F"), date).unwrap();
+
+        assert!((input.tmax() - input.tmin()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_input_rejects_pair_inverted_by_conversion() {
+        // Given, 51 F is 10.56 C, above the 10 C maximum though 51 > 10 before conversion
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+
+        // When
+        let result = Input::new(Value::new(10.0, "C"), Value::new(51.0, "F"), date);
+
+        assert!(matches!(
+            result,
+            Err(RefEtError::OutOfRange { field: "tmin", .. })
+        ));
+    }
+
+    #[test]
+    fn test_input_tolerance_absorbs_rounding() {
+        // Given, 50.001 F is 0.0006 C above the 10 C maximum
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let tmax = Value::new(10.0, "C");
+        let tmin = Value::new(50.001, "F");
+
+        // When
+        let input = Input::new_with_tolerance(tmax.clone(), tmin.clone(), date, 0.01).unwrap();
+
+        assert_eq!(input.tmin(), input.tmax());
+        assert!(Input::new(tmax, tmin, date).is_err());
+    }
+
+    #[test]
+    fn test_set_latitude_str() {
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut input = Input::new(Value::new(30.0, "C"), Value::new(10.0, "C"), date).unwrap();
+
+        input.set_latitude_str("40°24'36\"N").unwrap();
+
+        assert!((input.latitude().unwrap() - 40.41_f64.to_radians()).abs() < 1e-9);
+        assert!(input.set_latitude_str("forty north").is_err());
+    }
+
+    #[test]
+    fn test_set_latitude_with_units() {
+        // Given
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let mut degrees = Input::new(Value::new(30.0, "C"), Value::new(10.0, "C"), date).unwrap();
+        let mut radians = degrees.clone();
+
+        // When
+        degrees.set_latitude_with_units(40.41, "deg").unwrap();
+        radians.set_latitude_with_units(0.705, "rad").unwrap();
+
+        assert!((degrees.latitude().unwrap() - 0.7053).abs() < 0.0001);
+        assert!((radians.latitude().unwrap() - degrees.latitude().unwrap()).abs() < 0.001);
+        assert!(radians.set_latitude_with_units(40.41, "rad").is_err());
+        assert!(radians.set_latitude_with_units(95.0, "deg").is_err());
+        assert!(radians.set_latitude_with_units(40.41, "grad").is_err());
+    }
+}
@@ -1,11 +1,115 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod asce;
+#[cfg(feature = "std")]
+mod completeness;
+#[cfg(feature = "std")]
+mod config;
+#[cfg(feature = "std")]
 mod conversions;
+#[cfg(feature = "std")]
+mod crop;
+#[cfg(feature = "csv")]
+mod csv_input;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "std")]
 mod et;
+#[cfg(feature = "std")]
 mod eta;
+#[cfg(feature = "std")]
+mod forecast;
+#[cfg(feature = "std")]
+mod ghcn;
+#[cfg(feature = "std")]
+mod hourly;
+#[cfg(feature = "std")]
+mod input;
+#[cfg(feature = "std")]
+mod methods;
+mod raw;
+#[cfg(feature = "std")]
+mod result;
+#[cfg(feature = "std")]
+mod series;
+#[cfg(feature = "std")]
+mod station;
 
-pub use et::calculate_ref_et;
-pub use eta::{EaInput, Method};
+#[cfg(feature = "std")]
+pub use completeness::{input_completeness, Completeness, CompletenessTier};
+#[cfg(feature = "std")]
+pub use config::{verify_asce_compliance, EtCoefficients, RefEtConfig};
+#[cfg(feature = "std")]
+pub use conversions::{
+    c_to_f, day_of_year_from_str, degrees_to_radians, dewpoint_from_rh, ea_to_rh,
+    ea_to_specific_humidity, eto_to_etr, etr_to_eto, f_to_c, feet_to_meters, k_to_c, lang_to_mj,
+    mj_to_lang, monthly_ratio, mph_to_mps, mps_to_mph, pa_to_kpa, parse_latitude_dms,
+    radians_to_degrees, rh_from_dewpoint, rh_to_ea, specific_humidity_to_ea, watts_to_mj,
+    wind_run_to_speed, ETO_ETR_RATIO,
+};
+#[cfg(feature = "std")]
+pub use crop::{
+    apply_crop_coefficient, dual_crop_et, effective_rainfall_scs, evaporation_coefficient,
+    gross_irrigation_requirement, kc_for_stage, kc_from_gdd, net_irrigation_requirement,
+    water_stress_index, water_stress_index_batch, Crop, CropStage, EtAccumulator, GddKcCurve,
+};
+#[cfg(feature = "csv")]
+pub use csv_input::{read_inputs_csv, ColumnMapping};
+#[cfg(feature = "std")]
+pub use error::RefEtError;
+#[cfg(feature = "std")]
+pub use et::{
+    calc_fcd, calc_ra_checked, calc_ra_for_date, calc_rnl, calc_rnl_with_model, calc_rns,
+    calc_rns_with_albedo, calc_rs_from_cloud_cover, calc_rs_from_sunshine,
+    calc_rs_from_sunshine_fraction, calc_ws_to_height, calc_ws_to_height_over_crop,
+    calculate_ref_et, calculate_ref_et_detailed, calculate_ref_et_detailed_with_albedo,
+    calculate_ref_et_detailed_with_g, calculate_ref_et_in, calculate_ref_et_with_coeffs,
+    calculate_ref_et_with_config, calculate_ref_et_with_defaults, calculate_single_ref_et,
+    daylight_hours, es_batch, qc_solar_radiation, vapor_pressure_deficit, EmissivityModel, EtUnits,
+    RefEt, RefEtComponents, ReferenceCrop, RsDecision, SolarQcFlag,
+};
+#[cfg(feature = "std")]
+pub use eta::{EaInput, EaInputBuilder, Method};
+#[cfg(feature = "std")]
+pub use forecast::{forecast_et, ForecastDay};
+#[cfg(feature = "std")]
+pub use ghcn::{et_from_ghcn, GhcnDailyRecord};
+#[cfg(feature = "std")]
+pub use hourly::{
+    calc_ra_period, calculate_ref_et_hourly, calculate_ref_et_hourly_utc, soil_heat_flux,
+    solar_time,
+};
+#[cfg(feature = "std")]
+pub use input::{calculate_ref_et_input, calculate_ref_et_validated, Input, ValidatedInput, Value};
+#[cfg(feature = "std")]
+pub use methods::{
+    calculate_fao56_eto, calculate_hamon_pet, calculate_hargreaves_eto, calculate_jensen_haise_eto,
+    calculate_jensen_haise_eto_with_config, calculate_makkink, calculate_makkink_eto,
+    calculate_makkink_with_coefficient, calculate_makkink_with_config, calculate_oudin_pet,
+    calculate_priestley_taylor_eto, calculate_priestley_taylor_eto_with_alpha, calculate_turc_eto,
+    compare_methods, compute_all, jensen_haise_coefficients, AsceStandardized, EtComparison,
+    EtMethod, Fao56, Hamon, Hargreaves, JensenHaise, Makkink, Oudin, PriestleyTaylor,
+    ThornthwaiteModel, Turc,
+};
+pub use raw::calculate_ref_et_raw;
+#[cfg(feature = "std")]
+pub use result::{
+    calculate_ref_et_result, calculate_ref_et_robust, calculate_ref_et_with_uncertainty,
+    et_sensitivity, DegradationLevel, EstimatedInput, EtDriver, EtWithBounds, InputUncertainty,
+    RefEtResult,
+};
+#[cfg(feature = "rayon")]
+pub use series::calculate_ref_et_series_par;
+#[cfg(feature = "std")]
+pub use series::{
+    aggregate_annual, aggregate_monthly, calculate_ref_et_series, calculate_ref_et_series_mean,
+    et_climatology, fill_gaps_hold_forward, fill_gaps_linear, rolling_sum, smooth_clear_sky_index,
+    DayStats,
+};
+#[cfg(feature = "std")]
+pub use station::Station;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use chrono::Utc;
@@ -13,8 +117,7 @@ mod tests {
 
     #[test]
     fn test_calculate_ref_et() {
-        // let naive_date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
-        // let naive_datetime = naive_date.and_hms_opt(0, 0, 0).unwrap();
+        // Given, the Greeley, Colorado example day of the ASCE Standardized manual
         let output = Output::new_with_values(
             32.4,
             10.9,
@@ -27,11 +130,34 @@ mod tests {
             Some(3.0),
             1462.4,
             40.41_f64.to_radians(),
+            chrono::NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When
+        let et = calculate_ref_et(&output).unwrap();
+
+        assert!((et.short - 5.69).abs() < 0.01);
+        assert!((et.tall - 7.32).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_calculate_ref_et_missing_wind() {
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            None,
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
             Utc::now().date_naive(),
         );
-        let (short_et, tall_et) = calculate_ref_et(&output);
 
-        println!("Short-term ET: {}", short_et);
-        println!("Tall-term ET: {}", tall_et);
+        let result = calculate_ref_et(&output);
+        assert_eq!(result, Err(RefEtError::MissingField("ws")));
     }
 }
@@ -0,0 +1,1215 @@
+use crate::conversions::day_of_year;
+use crate::et::{
+    calc_atmospheric_pressure, calc_ra, calc_rn, calc_rnl_with_sigma, calc_rns_with_albedo,
+    calc_rso, calc_ws, components_from_values, daylight_hours, eo, es, es_slope, mean_temp,
+    psy_constant, resolve_rs, to_celsius, DailyValues,
+};
+use crate::{calculate_ref_et, calculate_single_ref_et, RefEtConfig, RefEtError, ReferenceCrop};
+use climate::output::Output;
+use std::fmt;
+
+/// Latent heat of vaporization in MJ/kg, used to convert radiation to mm of water.
+const LAMBDA: f64 = 2.45;
+
+/// Priestley-Taylor coefficient for well watered surfaces in humid climates.
+const PRIESTLEY_TAYLOR_ALPHA: f64 = 1.26;
+
+/// Stefan-Boltzmann constant in MJ/K4/m2/day as published in FAO-56.
+const FAO56_SIGMA: f64 = 4.903e-9;
+
+/// Calculates reference ET with the Makkink radiation method using the standard 0.65 coefficient.
+///
+/// # Arguments
+///
+/// * `input` - The output values, which must include measured solar radiation.
+///
+/// # Returns
+///
+/// * The Makkink reference ET in mm/day, or an error when Rs is missing.
+pub fn calculate_makkink(input: &Output) -> Result<f64, RefEtError> {
+    calculate_makkink_with_coefficient(input, 0.65)
+}
+
+/// Calculates reference ET with the original Makkink form: ET = 0.61 * (Δ / (Δ + γ)) * Rs / λ - 0.12.
+///
+/// This is the form most Dutch and Belgian tools report. Like `calculate_makkink` it needs
+/// measured Rs and never falls back to the Hargreaves-Samani estimate.
+///
+/// # Arguments
+///
+/// * `input` - The output values, which must include measured solar radiation.
+///
+/// # Returns
+///
+/// * The Makkink reference ET in mm/day, or an error when Rs is missing.
+pub fn calculate_makkink_eto(input: &Output) -> Result<f64, RefEtError> {
+    Ok(calculate_makkink_with_coefficient(input, 0.61)? - 0.12)
+}
+
+/// Calculates reference ET with the Makkink method: ET = c * (Δ / (Δ + γ)) * Rs / λ.
+///
+/// Makkink is the national standard in the Netherlands and suits humid, low-wind climates. It
+/// needs only temperature, elevation, and measured Rs, so Rs is never estimated here.
+///
+/// # Arguments
+///
+/// * `input` - The output values, which must include measured solar radiation.
+/// * `coefficient` - The Makkink coefficient `c`, 0.65 in the standard form.
+///
+/// # Returns
+///
+/// * The Makkink reference ET in mm/day, or an error when Rs is missing or the configured
+///   temperature units aren't recognized.
+pub fn calculate_makkink_with_coefficient(
+    input: &Output,
+    coefficient: f64,
+) -> Result<f64, RefEtError> {
+    calculate_makkink_with_config(input, coefficient, &RefEtConfig::default())
+}
+
+/// Calculates reference ET with the Makkink method like `calculate_makkink_with_coefficient`,
+/// reading the output temperatures in the configured units.
+///
+/// # Arguments
+///
+/// * `input` - The output values, which must include measured solar radiation.
+/// * `coefficient` - The Makkink coefficient `c`, 0.65 in the standard form.
+/// * `config` - The configuration giving the units of the output temperatures.
+///
+/// # Returns
+///
+/// * The Makkink reference ET in mm/day, or an error when Rs is missing or the configured
+///   temperature units aren't recognized.
+pub fn calculate_makkink_with_config(
+    input: &Output,
+    coefficient: f64,
+    config: &RefEtConfig,
+) -> Result<f64, RefEtError> {
+    let rs = input.get_rs().ok_or(RefEtError::MissingField("rs"))?;
+    let tmax = to_celsius(input.get_tmax(), &config.temperature_units)?;
+    let tmin = to_celsius(input.get_tmin(), &config.temperature_units)?;
+
+    Ok(makkink(
+        mean_temp(tmax, tmin),
+        rs,
+        input.get_z(),
+        coefficient,
+    ))
+}
+
+/// Calculates the FAO-56 Penman-Monteith grass reference ETo (FAO-56 Eq. 6).
+///
+/// This differs from the ASCE Standardized short reference only where the FAO-56 paper does: the
+/// Stefan-Boltzmann constant is 4.903e-9 and Rs/Rso has no lower limit in the cloudiness term
+/// (Eq. 39). Daily soil heat flux is zero and Rs is estimated with Hargreaves-Samani (Eq. 50) when
+/// it isn't measured, as in the ASCE path.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+///
+/// # Returns
+///
+/// * The grass reference ETo in mm/day, or an error when wind speed is missing or ea can't be
+///   calculated from the humidity inputs.
+pub fn calculate_fao56_eto(input: &Output) -> Result<f64, RefEtError> {
+    let config = RefEtConfig::default();
+    let values = DailyValues::from_output(input, &config)?;
+
+    Ok(fao56_eto(&values, &config))
+}
+
+fn fao56_eto(values: &DailyValues, config: &RefEtConfig) -> f64 {
+    let tmean = mean_temp(values.tmax, values.tmin);
+    let delta = es_slope(tmean);
+    let gamma = psy_constant(values.pressure());
+
+    let ra = calc_ra(values.latitude, values.doy);
+    let rso = calc_rso(ra, values.z);
+    let (rs, _) = resolve_rs(values, config, ra, rso);
+    let fcd = 1.35 * (rs / rso).min(1.0) - 0.35; // FAO-56 Eq. 39
+    let rnl = calc_rnl_with_sigma(fcd, values.ea, values.tmax, values.tmin, FAO56_SIGMA);
+    let rn = calc_rn(calc_rns_with_albedo(rs, 0.23), rnl);
+    let u2 = calc_ws(values.ws, values.wz);
+
+    (0.408 * delta * rn
+        + gamma * (900.0 / (tmean + 273.0)) * u2 * (es(values.tmax, values.tmin) - values.ea))
+        / (delta + gamma * (1.0 + 0.34 * u2))
+}
+
+/// Calculates reference ET with the Hargreaves-Samani temperature method (FAO-56 Eq. 52):
+/// ETo = 0.0023 * (Tmean + 17.8) * (Tmax - Tmin)^0.5 * Ra, with Ra as its evaporation equivalent.
+///
+/// This is the standard fallback for stations that only record temperature.
+///
+/// # Arguments
+///
+/// * `tmax` - Daily maximum air temperature in Celsius.
+/// * `tmin` - Daily minimum air temperature in Celsius.
+/// * `latitude_rad` - Latitude of the station in radians.
+/// * `doy` - Day of the year.
+///
+/// # Returns
+///
+/// * The Hargreaves-Samani reference ET in mm/day.
+pub fn calculate_hargreaves_eto(tmax: f64, tmin: f64, latitude_rad: f64, doy: u32) -> f64 {
+    let ra = calc_ra(latitude_rad, doy) / LAMBDA;
+
+    0.0023 * (mean_temp(tmax, tmin) + 17.8) * (tmax - tmin).max(0.0).sqrt() * ra
+}
+
+/// Calculates potential ET with the Hamon temperature method:
+/// PET = k * 0.165 * 216.7 * N * es / (Tmean + 273.3), with N the daylight hours in units of 12
+/// hours and es the saturation vapor pressure at Tmean in mb.
+///
+/// Hamon needs only mean temperature and the day length from the sunset hour angle (Eq. 27), so it
+/// suits hydrologic modeling at uninstrumented basins.
+///
+/// # Arguments
+///
+/// * `tmean` - Mean air temperature in Celsius.
+/// * `doy` - Day of the year.
+/// * `latitude_rad` - Latitude of the station in radians.
+/// * `k` - Calibration coefficient, 1.0 for the standard form.
+///
+/// # Returns
+///
+/// * The Hamon potential ET in mm/day.
+pub fn calculate_hamon_pet(tmean: f64, doy: u32, latitude_rad: f64, k: f64) -> f64 {
+    let daylight = daylight_hours(latitude_rad, doy) / 12.0; // in units of 12 hours
+    let es = 10.0 * eo(tmean); // kPa to mb
+
+    k * 0.165 * 216.7 * daylight * es / (tmean + 273.3)
+}
+
+/// Calculates the Oudin temperature-radiation potential ET (Oudin et al., 2005):
+/// PE = Re / (λρ) * (Tmean + 5) / 100 when Tmean + 5 > 0, otherwise 0.
+///
+/// Oudin was selected as the input PET for lumped rainfall-runoff models such as GR4J, which
+/// calibrate against streamflow and need only a simple, consistent daily PET.
+///
+/// # Arguments
+///
+/// * `tmean` - Mean air temperature in Celsius.
+/// * `latitude_rad` - Latitude of the station in radians.
+/// * `doy` - Day of the year.
+///
+/// # Returns
+///
+/// * The Oudin potential ET in mm/day, 0 on days colder than -5 °C.
+pub fn calculate_oudin_pet(tmean: f64, latitude_rad: f64, doy: u32) -> f64 {
+    if tmean + 5.0 <= 0.0 {
+        return 0.0;
+    }
+
+    // Re / λ in mm/day, the density of water converts the depth from m to mm
+    calc_ra(latitude_rad, doy) / LAMBDA * (tmean + 5.0) / 100.0
+}
+
+/// Middle day of the year and number of days of each month of a non-leap year.
+const MONTHS: [(u32, f64); 12] = [
+    (15, 31.0),
+    (46, 28.0),
+    (74, 31.0),
+    (105, 30.0),
+    (135, 31.0),
+    (166, 30.0),
+    (196, 31.0),
+    (227, 31.0),
+    (258, 30.0),
+    (288, 31.0),
+    (319, 30.0),
+    (349, 31.0),
+];
+
+/// Thornthwaite monthly potential ET fitted to a station's monthly mean temperature normals.
+///
+/// Thornthwaite needs only temperature, so it suits climatology work and aridity indices where
+/// monthly normals are all that is available.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThornthwaiteModel {
+    heat_index: f64, // annual heat index I, the sum of the monthly (T/5)^1.514
+    a: f64,          // exponent fitted to the heat index
+}
+
+impl ThornthwaiteModel {
+    /// Fits the model to the twelve monthly mean temperatures in Celsius, January first. Months at
+    /// or below freezing add nothing to the heat index.
+    pub fn from_monthly_means(temps: &[f64; 12]) -> Self {
+        let heat_index: f64 = temps
+            .iter()
+            .filter(|&&t| t > 0.0)
+            .map(|t| (t / 5.0).powf(1.514))
+            .sum();
+        let a = 6.75e-7 * heat_index.powi(3) - 7.71e-5 * heat_index.powi(2)
+            + 1.792e-2 * heat_index
+            + 0.49239;
+
+        ThornthwaiteModel { heat_index, a }
+    }
+
+    /// Calculates the potential ET for one month: 16 * (10T / I)^a, adjusted by N/12 for the
+    /// mean daylight hours and by the number of days in the month over 30.
+    ///
+    /// Months at or below freezing return 0, and above 26.5 °C the Willmott et al. (1985)
+    /// polynomial replaces the power law, which overestimates there.
+    ///
+    /// # Arguments
+    ///
+    /// * `month_mean_temp` - Mean temperature of the month in Celsius.
+    /// * `month` - The month, 1 for January to 12 for December.
+    /// * `latitude_rad` - Latitude of the station in radians.
+    ///
+    /// # Returns
+    ///
+    /// * The potential ET of the month in mm, or NaN when `month` is not 1 to 12.
+    pub fn pet(&self, month_mean_temp: f64, month: u32, latitude_rad: f64) -> f64 {
+        let Some(&(mid_doy, days)) = MONTHS.get((month as usize).wrapping_sub(1)) else {
+            return f64::NAN;
+        };
+
+        let unadjusted = if month_mean_temp <= 0.0 || self.heat_index <= 0.0 {
+            0.0
+        } else if month_mean_temp >= 26.5 {
+            -415.85 + 32.24 * month_mean_temp - 0.43 * month_mean_temp.powi(2)
+        } else {
+            16.0 * (10.0 * month_mean_temp / self.heat_index).powf(self.a)
+        };
+
+        let daylight = daylight_hours(latitude_rad, mid_doy);
+
+        unadjusted * (daylight / 12.0) * (days / 30.0)
+    }
+}
+
+/// Calculates reference ET with the Priestley-Taylor method using the standard 1.26 coefficient.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+///
+/// # Returns
+///
+/// * The Priestley-Taylor reference ET in mm/day, or an error when ea can't be calculated from
+///   the humidity inputs.
+pub fn calculate_priestley_taylor_eto(input: &Output) -> Result<f64, RefEtError> {
+    calculate_priestley_taylor_eto_with_alpha(input, PRIESTLEY_TAYLOR_ALPHA)
+}
+
+/// Calculates reference ET with the Priestley-Taylor method: ET = α * (Δ / (Δ + γ)) * (Rn - G) / λ.
+///
+/// Priestley-Taylor suits humid sites where the aerodynamic term is small. Wind is not used, and
+/// Rn is derived the same way as the ASCE path, so missing Rs uses the Hargreaves-Samani estimate
+/// and missing humidity uses the Tmin dewpoint estimate for net long-wave radiation. Daily G is
+/// zero.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `alpha` - The Priestley-Taylor coefficient α, 1.26 in the standard form.
+///
+/// # Returns
+///
+/// * The Priestley-Taylor reference ET in mm/day, or an error when ea can't be calculated from
+///   the humidity inputs.
+pub fn calculate_priestley_taylor_eto_with_alpha(
+    input: &Output,
+    alpha: f64,
+) -> Result<f64, RefEtError> {
+    let config = RefEtConfig::default();
+    // wind isn't part of the equation, so any placeholder keeps a calm record usable
+    let values = DailyValues::from_output_with_default_wind(input, &config, Some(0.0))?;
+    let components = components_from_values(&values, &config);
+
+    Ok(priestley_taylor(
+        components.tmean,
+        components.rn,
+        components.pressure,
+        alpha,
+    ))
+}
+
+/// Calculates reference ET with the Turc radiation-temperature method:
+/// ETo = 0.013 * (Tmean / (Tmean + 15)) * (23.88 * Rs + 50), multiplied by 1 + (50 - RH) / 70
+/// when the mean relative humidity is below 50%.
+///
+/// Turc was developed for monthly estimates in western Europe and suits humid and Mediterranean
+/// climates. Rs must be measured, and the humidity used for the low-humidity correction is
+/// ea / es from the crate's ea methods, so missing humidity uses the Tmin dewpoint estimate. The
+/// equation isn't defined for freezing days, which return 0.
+///
+/// # Arguments
+///
+/// * `input` - The output values, which must include measured solar radiation.
+///
+/// # Returns
+///
+/// * The Turc reference ET in mm/day, or an error when Rs is missing or ea can't be calculated
+///   from the humidity inputs.
+pub fn calculate_turc_eto(input: &Output) -> Result<f64, RefEtError> {
+    let rs = input.get_rs().ok_or(RefEtError::MissingField("rs"))?;
+    let config = RefEtConfig::default();
+    // wind isn't part of the equation, so any placeholder keeps a calm record usable
+    let values = DailyValues::from_output_with_default_wind(input, &config, Some(0.0))?;
+    let rh_mean = (100.0 * values.ea / es(values.tmax, values.tmin)).min(100.0);
+
+    Ok(turc(mean_temp(values.tmax, values.tmin), rs, rh_mean))
+}
+
+/// Calculates alfalfa reference ET with the Jensen-Haise method: ETr = Ct * (Tmean - Tx) * Rs / λ.
+///
+/// Jensen-Haise is kept for consistency with long western US records. Rs must be measured, there
+/// is no Hargreaves-Samani fallback. Ct and Tx are station constants, either the original
+/// Ct = 0.025 and Tx = -3.2 or those from the warmest month's mean temperatures with
+/// `jensen_haise_coefficients`, never from the day being calculated.
+///
+/// # Arguments
+///
+/// * `input` - The output values, which must include measured solar radiation.
+/// * `ct` - The temperature coefficient Ct in 1/C.
+/// * `tx` - The temperature axis intercept Tx in Celsius.
+///
+/// # Returns
+///
+/// * The Jensen-Haise reference ET in mm/day, or an error when Rs is missing.
+pub fn calculate_jensen_haise_eto(input: &Output, ct: f64, tx: f64) -> Result<f64, RefEtError> {
+    calculate_jensen_haise_eto_with_config(input, ct, tx, &RefEtConfig::default())
+}
+
+/// Calculates alfalfa reference ET with the Jensen-Haise method like `calculate_jensen_haise_eto`,
+/// reading the output temperatures in the configured units.
+///
+/// # Arguments
+///
+/// * `input` - The output values, which must include measured solar radiation.
+/// * `ct` - The temperature coefficient Ct in 1/C.
+/// * `tx` - The temperature axis intercept Tx in Celsius.
+/// * `config` - The configuration giving the units of the output temperatures.
+///
+/// # Returns
+///
+/// * The Jensen-Haise reference ET in mm/day, or an error when Rs is missing or the configured
+///   temperature units aren't recognized.
+pub fn calculate_jensen_haise_eto_with_config(
+    input: &Output,
+    ct: f64,
+    tx: f64,
+    config: &RefEtConfig,
+) -> Result<f64, RefEtError> {
+    let rs = input.get_rs().ok_or(RefEtError::MissingField("rs"))?;
+    let tmax = to_celsius(input.get_tmax(), &config.temperature_units)?;
+    let tmin = to_celsius(input.get_tmin(), &config.temperature_units)?;
+
+    Ok(ct * (mean_temp(tmax, tmin) - tx) * rs / LAMBDA)
+}
+
+/// Calculates the Jensen-Haise Ct and Tx coefficients (Jensen et al. 1990):
+/// Ct = 1 / (38 - 2z/305 + 7.3 * 50 / (e2 - e1)) and Tx = -2.5 - 0.14 (e2 - e1) - z/550, where e2
+/// and e1 are the saturation vapor pressures in mb at the mean maximum and minimum temperatures of
+/// the warmest month.
+///
+/// # Arguments
+///
+/// * `z` - Station elevation in meters.
+/// * `tmax_warmest` - Mean daily maximum temperature of the warmest month in Celsius.
+/// * `tmin_warmest` - Mean daily minimum temperature of the warmest month in Celsius.
+///
+/// # Returns
+///
+/// * A tuple of Ct in 1/C and Tx in Celsius.
+pub fn jensen_haise_coefficients(z: f64, tmax_warmest: f64, tmin_warmest: f64) -> (f64, f64) {
+    let spread = 10.0 * (eo(tmax_warmest) - eo(tmin_warmest)); // kPa to mb
+    let c1 = 38.0 - 2.0 * z / 305.0;
+    let ch = 50.0 / spread;
+
+    (1.0 / (c1 + 7.3 * ch), -2.5 - 0.14 * spread - z / 550.0)
+}
+
+/// A reference ET method that can be chosen at runtime, for comparing methods on the same data.
+pub trait EtMethod {
+    /// Calculates the method's reference ET in mm/day.
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError>;
+
+    /// Calculates the method's reference ET in mm/day, reading the output temperatures in the
+    /// configured units. Methods that don't read a configuration ignore it and return `eto`.
+    fn eto_with_config(&self, input: &Output, _config: &RefEtConfig) -> Result<f64, RefEtError> {
+        self.eto(input)
+    }
+
+    /// A short name for labeling results.
+    fn name(&self) -> &'static str;
+}
+
+/// The ASCE Standardized Penman-Monteith equation for one reference crop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsceStandardized {
+    pub crop: ReferenceCrop,
+}
+
+/// The FAO-56 Penman-Monteith grass reference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fao56;
+
+/// The Hargreaves-Samani temperature method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hargreaves;
+
+/// The Hamon temperature method with its calibration coefficient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hamon {
+    pub k: f64,
+}
+
+/// The Oudin temperature-radiation method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Oudin;
+
+/// The Priestley-Taylor method with its coefficient α.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriestleyTaylor {
+    pub alpha: f64,
+}
+
+/// The Makkink radiation method with its coefficient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Makkink {
+    pub coefficient: f64,
+}
+
+/// The Turc radiation-temperature method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Turc;
+
+/// The Jensen-Haise method with the station's Ct and Tx.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JensenHaise {
+    pub ct: f64,
+    pub tx: f64,
+}
+
+impl JensenHaise {
+    /// Creates the method with Ct and Tx from the station elevation and the mean daily maximum and
+    /// minimum temperatures of the warmest month, in Celsius.
+    pub fn from_warmest_month(z: f64, tmax_warmest: f64, tmin_warmest: f64) -> JensenHaise {
+        let (ct, tx) = jensen_haise_coefficients(z, tmax_warmest, tmin_warmest);
+        JensenHaise { ct, tx }
+    }
+}
+
+impl Default for JensenHaise {
+    /// The original constants of Jensen and Haise (1963), ETr = (0.025 T + 0.08) Rs.
+    fn default() -> Self {
+        JensenHaise {
+            ct: 0.025,
+            tx: -3.2,
+        }
+    }
+}
+
+impl Default for Hamon {
+    fn default() -> Self {
+        Hamon { k: 1.0 }
+    }
+}
+
+impl Default for PriestleyTaylor {
+    fn default() -> Self {
+        PriestleyTaylor {
+            alpha: PRIESTLEY_TAYLOR_ALPHA,
+        }
+    }
+}
+
+impl Default for Makkink {
+    fn default() -> Self {
+        Makkink { coefficient: 0.65 }
+    }
+}
+
+impl EtMethod for AsceStandardized {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        calculate_single_ref_et(input, self.crop)
+    }
+
+    fn name(&self) -> &'static str {
+        match self.crop {
+            ReferenceCrop::ShortGrass => "asce_short",
+            ReferenceCrop::TallAlfalfa => "asce_tall",
+        }
+    }
+}
+
+impl EtMethod for Fao56 {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        calculate_fao56_eto(input)
+    }
+
+    fn name(&self) -> &'static str {
+        "fao56"
+    }
+}
+
+impl EtMethod for Hargreaves {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        self.eto_with_config(input, &RefEtConfig::default())
+    }
+
+    fn eto_with_config(&self, input: &Output, config: &RefEtConfig) -> Result<f64, RefEtError> {
+        let (tmax, tmin, doy) = temperatures_and_doy(input, config)?;
+        Ok(calculate_hargreaves_eto(
+            tmax,
+            tmin,
+            input.get_latitude(),
+            doy,
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "hargreaves"
+    }
+}
+
+impl EtMethod for Hamon {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        self.eto_with_config(input, &RefEtConfig::default())
+    }
+
+    fn eto_with_config(&self, input: &Output, config: &RefEtConfig) -> Result<f64, RefEtError> {
+        let (tmax, tmin, doy) = temperatures_and_doy(input, config)?;
+        Ok(calculate_hamon_pet(
+            mean_temp(tmax, tmin),
+            doy,
+            input.get_latitude(),
+            self.k,
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "hamon"
+    }
+}
+
+impl EtMethod for Oudin {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        self.eto_with_config(input, &RefEtConfig::default())
+    }
+
+    fn eto_with_config(&self, input: &Output, config: &RefEtConfig) -> Result<f64, RefEtError> {
+        let (tmax, tmin, doy) = temperatures_and_doy(input, config)?;
+        Ok(calculate_oudin_pet(
+            mean_temp(tmax, tmin),
+            input.get_latitude(),
+            doy,
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "oudin"
+    }
+}
+
+impl EtMethod for PriestleyTaylor {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        calculate_priestley_taylor_eto_with_alpha(input, self.alpha)
+    }
+
+    fn name(&self) -> &'static str {
+        "priestley_taylor"
+    }
+}
+
+impl EtMethod for Makkink {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        calculate_makkink_with_coefficient(input, self.coefficient)
+    }
+
+    fn eto_with_config(&self, input: &Output, config: &RefEtConfig) -> Result<f64, RefEtError> {
+        calculate_makkink_with_config(input, self.coefficient, config)
+    }
+
+    fn name(&self) -> &'static str {
+        "makkink"
+    }
+}
+
+impl EtMethod for Turc {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        calculate_turc_eto(input)
+    }
+
+    fn name(&self) -> &'static str {
+        "turc"
+    }
+}
+
+impl EtMethod for JensenHaise {
+    fn eto(&self, input: &Output) -> Result<f64, RefEtError> {
+        calculate_jensen_haise_eto(input, self.ct, self.tx)
+    }
+
+    fn eto_with_config(&self, input: &Output, config: &RefEtConfig) -> Result<f64, RefEtError> {
+        calculate_jensen_haise_eto_with_config(input, self.ct, self.tx, config)
+    }
+
+    fn name(&self) -> &'static str {
+        "jensen_haise"
+    }
+}
+
+/// Runs every method over the same output for method intercomparison.
+///
+/// # Arguments
+///
+/// * `input` - The output values for the day.
+/// * `methods` - The methods to run.
+///
+/// # Returns
+///
+/// * The name and result of each method, in the order of `methods`.
+pub fn compute_all(
+    input: &Output,
+    methods: &[Box<dyn EtMethod>],
+) -> Vec<(&'static str, Result<f64, RefEtError>)> {
+    methods
+        .iter()
+        .map(|method| (method.name(), method.eto(input)))
+        .collect()
+}
+
+/// Reference ET in mm/day from each method for the same day, `None` where the day lacks a
+/// method's inputs, such as the radiation methods without measured Rs.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EtComparison {
+    pub asce_short: Option<f64>, // ASCE Standardized short reference, ETos
+    pub asce_tall: Option<f64>,  // ASCE Standardized tall reference, ETrs
+    pub fao56: Option<f64>,      // FAO-56 Penman-Monteith grass reference
+    pub hargreaves: Option<f64>, // Hargreaves-Samani temperature method
+    pub hamon: Option<f64>,      // Hamon temperature method with k = 1
+    pub oudin: Option<f64>,      // Oudin temperature-radiation method
+    pub priestley_taylor: Option<f64>, // Priestley-Taylor with α = 1.26
+    pub makkink: Option<f64>,    // Makkink with the 0.65 coefficient
+    pub turc: Option<f64>,       // Turc radiation-temperature method
+}
+
+impl EtComparison {
+    /// The CSV header matching `to_csv_row`.
+    pub const CSV_HEADER: &'static str =
+        "asce_short,asce_tall,fao56,hargreaves,hamon,oudin,priestley_taylor,makkink,turc";
+
+    /// Formats the methods as a CSV row in the order of `CSV_HEADER`, leaving missing methods empty.
+    pub fn to_csv_row(&self) -> String {
+        self.values()
+            .iter()
+            .map(|(_, value)| value.map_or_else(String::new, |et| format!("{:.2}", et)))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    fn values(&self) -> [(&'static str, Option<f64>); 9] {
+        [
+            ("asce_short", self.asce_short),
+            ("asce_tall", self.asce_tall),
+            ("fao56", self.fao56),
+            ("hargreaves", self.hargreaves),
+            ("hamon", self.hamon),
+            ("oudin", self.oudin),
+            ("priestley_taylor", self.priestley_taylor),
+            ("makkink", self.makkink),
+            ("turc", self.turc),
+        ]
+    }
+}
+
+impl fmt::Display for EtComparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, value) in self.values() {
+            match value {
+                Some(et) => writeln!(f, "{}: {:.2} mm/day", name, et)?,
+                None => writeln!(f, "{}: missing inputs", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Calculates every method that the day's data supports, for a method intercomparison table.
+///
+/// # Arguments
+///
+/// * `input` - The output values for the day.
+///
+/// # Returns
+///
+/// * The reference ET of each method, `None` for the methods whose inputs are missing.
+pub fn compare_methods(input: &Output) -> EtComparison {
+    let asce = calculate_ref_et(input).ok();
+
+    EtComparison {
+        asce_short: asce.map(|et| et.short),
+        asce_tall: asce.map(|et| et.tall),
+        fao56: Fao56.eto(input).ok(),
+        hargreaves: Hargreaves.eto(input).ok(),
+        hamon: Hamon::default().eto(input).ok(),
+        oudin: Oudin.eto(input).ok(),
+        priestley_taylor: PriestleyTaylor::default().eto(input).ok(),
+        makkink: Makkink::default().eto(input).ok(),
+        turc: Turc.eto(input).ok(),
+    }
+}
+
+/// The output temperatures in Celsius, read in the configured units, and the day of the year.
+fn temperatures_and_doy(
+    input: &Output,
+    config: &RefEtConfig,
+) -> Result<(f64, f64, u32), RefEtError> {
+    let doy = day_of_year(&input.get_date()).map_err(|_| RefEtError::MissingField("date"))?;
+
+    Ok((
+        to_celsius(input.get_tmax(), &config.temperature_units)?,
+        to_celsius(input.get_tmin(), &config.temperature_units)?,
+        doy,
+    ))
+}
+
+fn turc(tmean: f64, rs: f64, rh_mean: f64) -> f64 {
+    if tmean <= 0.0 {
+        return 0.0;
+    }
+
+    let et = 0.013 * (tmean / (tmean + 15.0)) * (23.88 * rs + 50.0);
+    if rh_mean < 50.0 {
+        et * (1.0 + (50.0 - rh_mean) / 70.0)
+    } else {
+        et
+    }
+}
+
+fn priestley_taylor(tmean: f64, rn: f64, pressure: f64, alpha: f64) -> f64 {
+    let delta = es_slope(tmean);
+    let gamma = psy_constant(pressure);
+
+    alpha * (delta / (delta + gamma)) * rn / LAMBDA
+}
+
+fn makkink(tmean: f64, rs: f64, z: f64, coefficient: f64) -> f64 {
+    let delta = es_slope(tmean);
+    let gamma = psy_constant(calc_atmospheric_pressure(z));
+
+    coefficient * (delta / (delta + gamma)) * rs / LAMBDA
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_makkink_sea_level() {
+        // Given, a 20 C day at sea level with 20 MJ/m2/day of Rs
+        let tmean = 20.0;
+        let rs = 20.0;
+        let z = 0.0;
+
+        // When
+        let et = makkink(tmean, rs, z, 0.65);
+
+        // de Bruin and Lablans (1998) with the FAO-56 Annex 2 table values Δ = 0.145 kPa/C
+        // (Table 2.4, 20 C) and γ = 0.067 kPa/C (Table 2.2, 0 m): 0.65 * 0.145 / 0.212 * 20 / 2.45
+        assert!((et - 3.629).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_makkink_coefficient_scales() {
+        let standard = makkink(21.65, 22.4, 1462.4, 0.65);
+        let scaled = makkink(21.65, 22.4, 1462.4, 0.61);
+
+        assert!((standard - 4.376).abs() < 0.001);
+        assert!((scaled / standard - 0.61 / 0.65).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_makkink_eto_temperate() {
+        // Given, a sunny summer day at a Dutch coastal station
+        let output = Output::new_with_values(
+            22.0,
+            12.0,
+            None,
+            None,
+            None,
+            None,
+            Some(18.0),
+            None,
+            None,
+            0.0,
+            52.1_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2001, 7, 15).unwrap(),
+        );
+
+        // When
+        let et = calculate_makkink_eto(&output).unwrap();
+
+        // hand calculation: delta = 0.1228, gamma = 0.0674, 0.61 * 0.6457 * 18 / 2.45 - 0.12
+        assert!((et - 2.774).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_priestley_taylor_sea_level() {
+        // Given, a humid sea level day
+        let tmean = 20.0;
+        let rn = 15.0;
+        let pressure = calc_atmospheric_pressure(0.0);
+
+        // When
+        let et = priestley_taylor(tmean, rn, pressure, PRIESTLEY_TAYLOR_ALPHA);
+
+        // hand calculation: delta = 0.1447, gamma = 0.0674, 1.26 * 0.6824 * 15 / 2.45
+        assert!((et - 5.264).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fao56_eto_brussels() {
+        // Given, FAO-56 Example 18 (Brussels, 6 July) with Rs from its sunshine hours
+        let output = Output::new_with_values(
+            21.5,
+            12.3,
+            Some(84.0),
+            Some(63.0),
+            None,
+            None,
+            Some(22.07),
+            Some(2.78),
+            Some(10.0),
+            100.0,
+            50.80_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2001, 7, 6).unwrap(),
+        );
+
+        // When
+        let eto = calculate_fao56_eto(&output).unwrap();
+
+        // FAO-56 gives 3.9 mm/day
+        assert!((eto - 3.9).abs() < 0.1);
+    }
+
+    fn turc_output(tmax: f64, tmin: f64, ea: f64, rs: f64) -> Output {
+        Output::new_with_values(
+            tmax,
+            tmin,
+            None,
+            None,
+            None,
+            Some(ea),
+            Some(rs),
+            None,
+            None,
+            0.0,
+            40.0_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2001, 7, 15).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_turc_eto_humid() {
+        // Given, RH of about 78% so the standard form applies
+        let output = turc_output(25.0, 15.0, 1.9, 20.0);
+
+        // When
+        let et = calculate_turc_eto(&output).unwrap();
+
+        // hand calculation: 0.013 * 20 / 35 * (23.88 * 20 + 50)
+        assert!((et - 3.919).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_turc_eto_arid() {
+        // Given, RH of about 27% so the low-humidity correction applies
+        let output = turc_output(35.0, 15.0, 1.0, 25.0);
+
+        // When
+        let et = calculate_turc_eto(&output).unwrap();
+
+        // hand calculation: 0.013 * 25 / 40 * (23.88 * 25 + 50) * (1 + (50 - 27.29) / 70)
+        assert!((et - 6.962).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_jensen_haise_coefficients_greeley() {
+        // Given, the Greeley reference day standing in for the warmest month
+        let (ct, tx) = jensen_haise_coefficients(1462.4, 32.4, 10.9);
+
+        // hand calculation: e2 - e1 = 35.59 mb, C1 = 28.41, CH = 1.405
+        assert!((ct - 0.02586).abs() < 0.00001);
+        assert!((tx - -10.14).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_jensen_haise_eto_published_constants() {
+        // Given, the original constants of Jensen and Haise (1963), ETr = (0.025 T + 0.08) Rs with
+        // Rs as an evaporation equivalent in mm/day
+        let output = turc_output(30.0, 20.0, 1.5, 25.0);
+        let method = JensenHaise::default();
+
+        // When
+        let et = method.eto(&output).unwrap();
+
+        // the published form with T = 25 C and Rs = 25 / 2.45 mm/day
+        assert!((et - (0.025 * 25.0 + 0.08) * 25.0 / 2.45).abs() < 1e-9);
+        assert!((et - 7.194).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_jensen_haise_eto_greeley() {
+        // Given
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When, with the Greeley reference day standing in for the warmest month's means
+        let method = JensenHaise::from_warmest_month(1462.4, 32.4, 10.9);
+        let et = method.eto(&output).unwrap();
+
+        // within 3% of the ASCE Standardized tall reference of 7.32 mm/day
+        assert!((et - 7.518).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_hamon_pet_temperate_july() {
+        // Given, a 20 C mid-July day at 40 N with 14.57 hours of daylight
+        let latitude = 40.0_f64.to_radians();
+
+        // When
+        let pet = calculate_hamon_pet(20.0, 196, latitude, 1.0);
+
+        // hand calculation: 0.165 * 216.7 * 1.214 * 23.37 / 293.3, about 107 mm for the month
+        assert!((pet - 3.461).abs() < 0.001);
+        assert!((calculate_hamon_pet(20.0, 196, latitude, 1.2) - 1.2 * pet).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_oudin_pet() {
+        // Given, a 20 C day at Greeley in early July with Re of 41.63 MJ/m2/day
+        let latitude = 40.41_f64.to_radians();
+
+        // When
+        let pet = calculate_oudin_pet(20.0, latitude, 183);
+
+        // hand calculation: 41.626 / 2.45 * 25 / 100
+        assert!((pet - 4.248).abs() < 0.001);
+        assert_eq!(calculate_oudin_pet(-5.0, latitude, 183), 0.0);
+        assert_eq!(calculate_oudin_pet(-12.0, latitude, 183), 0.0);
+    }
+
+    #[test]
+    fn test_oudin_pet_fao56_example_8() {
+        // Given, the site of FAO-56 Example 8, 20 S on 3 September, with a mean of 20 C
+        let latitude = (-20.0_f64).to_radians();
+
+        // When
+        let pet = calculate_oudin_pet(20.0, latitude, 246);
+
+        // Oudin et al. (2005) with the published Re of Example 8, 32.2 MJ/m2/day:
+        // 32.2 / 2.45 * (20 + 5) / 100 = 3.286 mm/day
+        assert!((pet - 3.286).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_thornthwaite_annual_pet() {
+        // Given, monthly normals of a humid mid-Atlantic station at 39.5 N
+        let temps = [
+            0.4, 0.6, 5.2, 10.8, 16.7, 21.6, 24.4, 23.5, 19.7, 13.6, 7.3, 1.6,
+        ];
+        let latitude = 39.5_f64.to_radians();
+
+        // When
+        let model = ThornthwaiteModel::from_monthly_means(&temps);
+        let monthly: Vec<f64> = (1..=12)
+            .map(|month| model.pet(temps[month as usize - 1], month, latitude))
+            .collect();
+
+        // hand calculation: I = 55.61, a = 1.367, July = 150.95 mm, annual = 721.3 mm
+        assert!((model.heat_index - 55.61).abs() < 0.01);
+        assert!((model.a - 1.367).abs() < 0.001);
+        assert!((monthly[6] - 150.95).abs() < 0.01);
+        assert!((monthly.iter().sum::<f64>() - 721.3).abs() < 0.1);
+        assert!(model.pet(20.0, 13, latitude).is_nan());
+    }
+
+    #[test]
+    fn test_thornthwaite_standard_month() {
+        // Given, a station with a heat index of 100 on the equator, where every day has 12 hours
+        // of daylight, and any exponent since 1^a is 1
+        let model = ThornthwaiteModel {
+            heat_index: 100.0,
+            a: 2.2,
+        };
+
+        // When, a mean temperature of I/10 so that 10T/I = 1
+        let april = model.pet(10.0, 4, 0.0);
+        let july = model.pet(10.0, 7, 0.0);
+
+        // Thornthwaite (1948) defines 1.6 cm for a 30 day month of 12 hour days at 10T/I = 1
+        assert!((april - 16.0).abs() < 0.01);
+        assert!((july - 16.0 * 31.0 / 30.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_compute_all_three_methods() {
+        // Given, the Greeley reference day
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+        let methods: Vec<Box<dyn EtMethod>> = vec![
+            Box::new(AsceStandardized {
+                crop: ReferenceCrop::ShortGrass,
+            }),
+            Box::new(Hargreaves),
+            Box::new(PriestleyTaylor::default()),
+        ];
+
+        // When
+        let results = compute_all(&output, &methods);
+
+        let names: Vec<&str> = results.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["asce_short", "hargreaves", "priestley_taylor"]);
+        assert!((results[0].1.clone().unwrap() - 5.69).abs() < 0.01);
+        assert!((results[1].1.clone().unwrap() - 7.15).abs() < 0.01);
+        assert_eq!(results[2].1, calculate_priestley_taylor_eto(&output));
+    }
+
+    #[test]
+    fn test_methods_read_configured_temperature_units() {
+        // Given, the Greeley reference day with the temperatures recorded in Fahrenheit
+        let date = NaiveDate::from_ymd_opt(2000, 7, 1).unwrap();
+        let latitude = 40.41_f64.to_radians();
+        let celsius = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            latitude,
+            date,
+        );
+        let fahrenheit = Output::new_with_values(
+            90.32,
+            51.62,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            latitude,
+            date,
+        );
+        let config = RefEtConfig {
+            temperature_units: "F".to_string(),
+            ..RefEtConfig::default()
+        };
+        let methods: Vec<Box<dyn EtMethod>> = vec![
+            Box::new(Hargreaves),
+            Box::new(Hamon::default()),
+            Box::new(Oudin),
+            Box::new(Makkink::default()),
+            Box::new(JensenHaise::default()),
+        ];
+
+        for method in &methods {
+            // When
+            let expected = method.eto(&celsius).unwrap();
+            let et = method.eto_with_config(&fahrenheit, &config).unwrap();
+
+            assert!((et - expected).abs() < 1e-9, "{}", method.name());
+        }
+        assert!(calculate_makkink_with_config(&fahrenheit, 0.65, &config).is_ok());
+        assert!(calculate_jensen_haise_eto_with_config(&fahrenheit, 0.025, -3.2, &config).is_ok());
+    }
+
+    #[test]
+    fn test_hargreaves_eto_greeley() {
+        // Given, the Greeley reference day where Ra = 41.63 MJ/m2/day (16.99 mm/day)
+        let latitude = 40.41_f64.to_radians();
+
+        // When
+        let eto = calculate_hargreaves_eto(32.4, 10.9, latitude, 183);
+
+        // hand calculation: 0.0023 * (21.65 + 17.8) * 21.5^0.5 * 16.99
+        assert!((eto - 7.15).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_hargreaves_eto_fao56_example_8() {
+        // Given, the site of FAO-56 Example 8, 20 S on 3 September, with a 31 C / 19 C day
+        let latitude = (-20.0_f64).to_radians();
+
+        // When
+        let eto = calculate_hargreaves_eto(31.0, 19.0, latitude, 246);
+
+        // FAO-56 Eq. 52 with the published Ra of Example 8, 32.2 MJ/m2/day or 13.1 mm/day:
+        // 0.0023 * (25 + 17.8) * 12^0.5 * 13.1 = 4.467 mm/day, within the rounding of Ra
+        assert!((eto - 4.467).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_compare_methods_greeley() {
+        // Given
+        let output = Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            Some(1.94),
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        );
+
+        // When
+        let comparison = compare_methods(&output);
+
+        let values = comparison.values();
+        assert!(values
+            .iter()
+            .filter_map(|(_, value)| *value)
+            .all(|et| et.is_finite() && (0.0..=15.0).contains(&et)));
+        assert!((comparison.asce_short.unwrap() - 5.69).abs() < 0.01);
+        assert!((comparison.hargreaves.unwrap() - 7.15).abs() < 0.01);
+        assert_eq!(
+            comparison.to_csv_row().split(',').count(),
+            EtComparison::CSV_HEADER.split(',').count()
+        );
+    }
+}
@@ -0,0 +1,618 @@
+use crate::et::{
+    calc_ra, calc_rso, es, mean_temp, ref_et_from_values, resolve_rs, DailyValues, RsDecision,
+};
+use crate::{input_completeness, RefEtConfig, RefEtError};
+use climate::output::Output;
+
+/// Wind speed in m/s at 2 m that FAO-56 recommends when no wind data is available.
+const DEFAULT_WIND_SPEED: f64 = 2.0;
+
+/// Relative change applied to Rs, wind, and humidity for the finite-difference sensitivities.
+const PERTURBATION: f64 = 0.05;
+
+/// Change in Kelvin applied to temperatures for the finite-difference sensitivities. Temperatures
+/// are changed by a fixed step rather than a fraction, since a fraction of a Celsius value depends
+/// on the units and vanishes at 0 °C.
+const TEMPERATURE_STEP: f64 = 1.0;
+
+/// Difference between Kelvin and Celsius.
+const KELVIN_OFFSET: f64 = 273.15;
+
+/// The input group that the reference ET is most sensitive to on a given day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EtDriver {
+    Temperature,
+    Radiation,
+    Wind,
+    Humidity,
+}
+
+/// An input that was estimated rather than measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EstimatedInput {
+    Rs,   // Hargreaves-Samani estimate from the temperature range
+    Ea,   // dewpoint estimated from Tmin
+    Wind, // FAO-56 default of 2 m/s
+}
+
+/// How much of a reference ET value rests on estimated rather than measured inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DegradationLevel {
+    Full,                           // every input was measured
+    Estimated(Vec<EstimatedInput>), // the listed inputs were estimated
+    Minimal,                        // only temperature was measured, everything else estimated
+}
+
+/// Short and tall reference ET with diagnostics about how they were produced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefEtResult {
+    pub short: f64,               // short (grass) reference ET in mm/day
+    pub tall: f64,                // tall (alfalfa) reference ET in mm/day
+    pub dominant_input: EtDriver, // input with the largest ET response to a typical error
+    pub rs_decision: RsDecision,  // how the solar radiation for the day was obtained
+}
+
+impl RefEtResult {
+    /// Describes each field as (field, unit, description) so front-ends can label results
+    /// without hardcoding them.
+    pub fn field_metadata() -> &'static [(&'static str, &'static str, &'static str)] {
+        &[
+            (
+                "short",
+                "mm/day",
+                "short (grass) reference evapotranspiration",
+            ),
+            (
+                "tall",
+                "mm/day",
+                "tall (alfalfa) reference evapotranspiration",
+            ),
+            (
+                "dominant_input",
+                "",
+                "input group with the largest ET response to a typical error",
+            ),
+            (
+                "rs_decision",
+                "",
+                "how the solar radiation for the day was obtained",
+            ),
+        ]
+    }
+}
+
+/// Calculates the short and tall reference et along with the input that dominates the day's ET.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+///
+/// # Returns
+///
+/// * A `RefEtResult`, or an error when the inputs can't produce an ea value.
+pub fn calculate_ref_et_result(input: &Output) -> Result<RefEtResult, RefEtError> {
+    let config = RefEtConfig::default();
+    let values = DailyValues::from_output(input, &config)?;
+    let (short, tall) = ref_et_from_values(&values, &config);
+    let ra = calc_ra(values.latitude, values.doy);
+    let (_, rs_decision) = resolve_rs(&values, &config, ra, calc_rso(ra, values.z));
+
+    Ok(RefEtResult {
+        short,
+        tall,
+        dominant_input: dominant_input(&values, &config),
+        rs_decision,
+    })
+}
+
+/// Measurement uncertainty of each input as a ± value in the units of the ASCE Standardized
+/// equation. The default is no uncertainty in any input.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InputUncertainty {
+    pub tmax: f64, // ± daily maximum air temperature in Celsius
+    pub tmin: f64, // ± daily minimum air temperature in Celsius
+    pub rs: f64,   // ± measured solar radiation in MJ/m2/day
+    pub ws: f64,   // ± wind speed in m/s at the measurement height
+    pub ea: f64,   // ± actual vapor pressure in kPa
+}
+
+/// Short and tall reference ET with the range their input uncertainty allows, in mm/day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EtWithBounds {
+    pub short_low: f64, // short reference ET less the propagated uncertainty, not below 0
+    pub short_mid: f64, // short reference ET from the inputs as measured
+    pub short_high: f64, // short reference ET plus the propagated uncertainty
+    pub tall_low: f64,  // tall reference ET less the propagated uncertainty, not below 0
+    pub tall_mid: f64,  // tall reference ET from the inputs as measured
+    pub tall_high: f64, // tall reference ET plus the propagated uncertainty
+}
+
+/// Calculates the short and tall reference et with a band for the uncertainty of the inputs.
+///
+/// The uncertainty is propagated to first order: each input is moved by its ± value in turn with
+/// the others held, half the change in ET between the two is taken as that input's contribution,
+/// and the contributions are combined as a root sum of squares. The inputs are treated as
+/// independent. An Rs uncertainty only applies when Rs is measured.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+/// * `err` - The ± uncertainty of each input.
+///
+/// # Returns
+///
+/// * The low, mid, and high values of both references, or an error when wind speed is missing or
+///   ea can't be calculated from the humidity inputs.
+pub fn calculate_ref_et_with_uncertainty(
+    input: &Output,
+    err: &InputUncertainty,
+) -> Result<EtWithBounds, RefEtError> {
+    let config = RefEtConfig::default();
+    let values = DailyValues::from_output(input, &config)?;
+    let (short, tall) = ref_et_from_values(&values, &config);
+
+    let shifted = |sign: f64| {
+        [
+            DailyValues {
+                tmax: values.tmax + sign * err.tmax,
+                ..values
+            },
+            DailyValues {
+                tmin: values.tmin + sign * err.tmin,
+                ..values
+            },
+            DailyValues {
+                rs: values.rs.map(|rs| (rs + sign * err.rs).max(0.0)),
+                ..values
+            },
+            DailyValues {
+                ws: (values.ws + sign * err.ws).max(0.0),
+                ..values
+            },
+            DailyValues {
+                ea: (values.ea + sign * err.ea).max(0.0),
+                ..values
+            },
+        ]
+    };
+
+    let (mut short_variance, mut tall_variance) = (0.0, 0.0);
+    for (up, down) in shifted(1.0).iter().zip(&shifted(-1.0)) {
+        let (short_up, tall_up) = ref_et_from_values(up, &config);
+        let (short_down, tall_down) = ref_et_from_values(down, &config);
+        short_variance += ((short_up - short_down) / 2.0).powi(2);
+        tall_variance += ((tall_up - tall_down) / 2.0).powi(2);
+    }
+    let (short_band, tall_band) = (short_variance.sqrt(), tall_variance.sqrt());
+
+    Ok(EtWithBounds {
+        short_low: (short - short_band).max(0.0),
+        short_mid: short,
+        short_high: short + short_band,
+        tall_low: (tall - tall_band).max(0.0),
+        tall_mid: tall,
+        tall_high: tall + tall_band,
+    })
+}
+
+/// Calculates the short and tall reference et using every available fallback and reports how
+/// degraded the result is.
+///
+/// Missing Rs uses the Hargreaves-Samani estimate, missing humidity uses the Tmin dewpoint estimate,
+/// and missing wind uses 2 m/s at 2 m. This is intended for bulk processing of messy records where
+/// a value with a trust level is more useful than an error.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+///
+/// # Returns
+///
+/// * A tuple of the short and tall reference ET and the degradation level, or an error when the
+///   record can't be used at all, such as unrecognized humidity units or a negative Rs.
+pub fn calculate_ref_et_robust(input: &Output) -> Result<(f64, f64, DegradationLevel), RefEtError> {
+    let config = RefEtConfig::default();
+    let completeness = input_completeness(input);
+
+    let values =
+        DailyValues::from_output_with_default_wind(input, &config, Some(DEFAULT_WIND_SPEED))?;
+
+    let mut estimated = Vec::new();
+    if !completeness.radiation {
+        estimated.push(EstimatedInput::Rs);
+    }
+    if !completeness.humidity {
+        estimated.push(EstimatedInput::Ea);
+    }
+    if !completeness.wind {
+        estimated.push(EstimatedInput::Wind);
+    }
+
+    let level = match estimated.len() {
+        0 => DegradationLevel::Full,
+        3 => DegradationLevel::Minimal,
+        _ => DegradationLevel::Estimated(estimated),
+    };
+    let (short, tall) = ref_et_from_values(&values, &config);
+
+    Ok((short, tall, level))
+}
+
+/// Ranks the measured inputs by their influence on the short reference ET.
+///
+/// Each sensitivity is the normalized partial derivative ∂ET/∂x · x/ET from central differences
+/// in one input with the others held, so 0.5 means a 10% error in the input moves ET by 5%. Rs, ws,
+/// and ea are changed by ±5%. Temperatures are changed by ±1 K and normalized by the temperature
+/// in Kelvin, so the result doesn't depend on the input units and stays meaningful at or below
+/// 0 °C. Unlike the grouped drivers of `calculate_ref_et_result`, temperature changes here also
+/// change the vapor pressure deficit. Rs has no sensitivity when it is estimated rather than
+/// measured.
+///
+/// # Arguments
+///
+/// * `input` - The Input values for temperature, relative humidity, and air pressure.
+///
+/// # Returns
+///
+/// * The sensitivity of rs, ws, tmax, tmin, and ea, sorted from the largest magnitude to the
+///   smallest, or an error when wind speed is missing or ea can't be calculated.
+pub fn et_sensitivity(input: &Output) -> Result<Vec<(&'static str, f64)>, RefEtError> {
+    let config = RefEtConfig::default();
+    let values = DailyValues::from_output(input, &config)?;
+
+    let inputs: [(&'static str, Perturb); 5] = [
+        ("rs", perturb_radiation),
+        ("ws", perturb_wind),
+        ("tmax", |values, direction| {
+            let step = direction * TEMPERATURE_STEP;
+            let perturbed = DailyValues {
+                tmax: values.tmax + step,
+                ..*values
+            };
+            (perturbed, step / (values.tmax + KELVIN_OFFSET))
+        }),
+        ("tmin", |values, direction| {
+            let step = direction * TEMPERATURE_STEP;
+            let perturbed = DailyValues {
+                tmin: values.tmin + step,
+                ..*values
+            };
+            (perturbed, step / (values.tmin + KELVIN_OFFSET))
+        }),
+        ("ea", |values, direction| {
+            let step = direction * PERTURBATION;
+            let perturbed = DailyValues {
+                ea: values.ea * (1.0 + step),
+                ..*values
+            };
+            (perturbed, step)
+        }),
+    ];
+
+    let mut sensitivities: Vec<(&'static str, f64)> = inputs
+        .iter()
+        .map(|&(name, perturb)| (name, sensitivity(&values, &config, perturb)))
+        .collect();
+    sensitivities.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+
+    Ok(sensitivities)
+}
+
+/// Finds the input group that moves the short reference ET the most for a typical error in it,
+/// using central differences around the actual inputs of ±5% for radiation, wind, and the vapor
+/// pressure deficit, and ±1 K for temperature. The response is compared as the relative change in
+/// ET per step rather than as an elasticity, since an elasticity to absolute temperature in Kelvin
+/// would rank temperature first on nearly every day.
+///
+/// Temperature is perturbed with the vapor pressure deficit held constant and humidity is
+/// perturbed through the vapor pressure deficit, so the warming effect on the deficit is counted
+/// once, as humidity, rather than in both groups. Radiation uses the measured Rs, or the
+/// Hargreaves-Samani estimate through temperature when Rs is missing.
+fn dominant_input(values: &DailyValues, config: &RefEtConfig) -> EtDriver {
+    let drivers = [
+        (
+            EtDriver::Temperature,
+            step_response(values, config, perturb_temperature),
+        ),
+        (
+            EtDriver::Radiation,
+            step_response(values, config, perturb_radiation),
+        ),
+        (EtDriver::Wind, step_response(values, config, perturb_wind)),
+        (
+            EtDriver::Humidity,
+            step_response(values, config, perturb_humidity),
+        ),
+    ];
+
+    drivers
+        .iter()
+        .fold(drivers[0], |best, &driver| {
+            if driver.1.abs() > best.1.abs() {
+                driver
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+/// Changes one input of the day by one step up (direction 1) or down (direction -1), returning the
+/// changed values and the relative change Δx/x of the input.
+type Perturb = fn(&DailyValues, f64) -> (DailyValues, f64);
+
+/// The normalized sensitivity ∂ET/∂x · x/ET of the short reference ET to one input.
+fn sensitivity(values: &DailyValues, config: &RefEtConfig, perturb: Perturb) -> f64 {
+    let (relative_up, relative_down) = (perturb(values, 1.0).1, perturb(values, -1.0).1);
+
+    step_response(values, config, perturb) * 2.0 / (relative_up - relative_down)
+}
+
+/// The relative change in the short reference ET for one step of an input, from central
+/// differences.
+fn step_response(values: &DailyValues, config: &RefEtConfig, perturb: Perturb) -> f64 {
+    let et = ref_et_from_values(values, config).0;
+    if et.abs() < f64::EPSILON {
+        return 0.0;
+    }
+
+    let et_up = ref_et_from_values(&perturb(values, 1.0).0, config).0;
+    let et_down = ref_et_from_values(&perturb(values, -1.0).0, config).0;
+
+    (et_up - et_down) / (2.0 * et)
+}
+
+fn perturb_temperature(values: &DailyValues, direction: f64) -> (DailyValues, f64) {
+    let step = direction * TEMPERATURE_STEP;
+    let vpd = es(values.tmax, values.tmin) - values.ea;
+    let tmax = values.tmax + step;
+    let tmin = values.tmin + step;
+
+    let perturbed = DailyValues {
+        tmax,
+        tmin,
+        ea: (es(tmax, tmin) - vpd).max(0.0),
+        ..*values
+    };
+    (
+        perturbed,
+        step / (mean_temp(values.tmax, values.tmin) + KELVIN_OFFSET),
+    )
+}
+
+fn perturb_radiation(values: &DailyValues, direction: f64) -> (DailyValues, f64) {
+    let step = direction * PERTURBATION;
+    let perturbed = DailyValues {
+        rs: values.rs.map(|rs| rs * (1.0 + step)),
+        ..*values
+    };
+    (perturbed, step)
+}
+
+fn perturb_wind(values: &DailyValues, direction: f64) -> (DailyValues, f64) {
+    let step = direction * PERTURBATION;
+    let perturbed = DailyValues {
+        ws: values.ws * (1.0 + step),
+        ..*values
+    };
+    (perturbed, step)
+}
+
+fn perturb_humidity(values: &DailyValues, direction: f64) -> (DailyValues, f64) {
+    let step = direction * PERTURBATION;
+    let saturation = es(values.tmax, values.tmin);
+
+    let perturbed = DailyValues {
+        ea: (saturation - (saturation - values.ea) * (1.0 + step)).max(0.0),
+        ..*values
+    };
+    (perturbed, step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn greeley_output(ea: Option<f64>, rs: Option<f64>, ws: Option<f64>) -> Output {
+        Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            ea,
+            rs,
+            ws,
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, 1).unwrap(),
+        )
+    }
+
+    fn values(tmax: f64, tmin: f64, ea: f64, rs: f64, ws: f64) -> DailyValues {
+        DailyValues {
+            tmax,
+            tmin,
+            ea,
+            rs: Some(rs),
+            rn: None,
+            sunshine: None,
+            cloud: None,
+            ws,
+            wz: 2.0,
+            z: 1462.4,
+            pressure: None,
+            latitude: 40.41_f64.to_radians(),
+            doy: 183,
+            g: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_uncertainty_zero_collapses_band() {
+        // Given
+        let output = greeley_output(Some(1.27), Some(22.4), Some(1.94));
+
+        // When
+        let bounds =
+            calculate_ref_et_with_uncertainty(&output, &InputUncertainty::default()).unwrap();
+
+        assert_eq!(bounds.short_low, bounds.short_mid);
+        assert_eq!(bounds.short_mid, bounds.short_high);
+        assert_eq!(bounds.tall_low, bounds.tall_high);
+        assert!((bounds.short_mid - 5.69).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_uncertainty_widens_band() {
+        // Given, typical sensor accuracy
+        let output = greeley_output(Some(1.27), Some(22.4), Some(1.94));
+        let err = InputUncertainty {
+            tmax: 0.5,
+            tmin: 0.5,
+            rs: 1.0,
+            ws: 0.2,
+            ea: 0.05,
+        };
+
+        // When
+        let bounds = calculate_ref_et_with_uncertainty(&output, &err).unwrap();
+
+        assert!(bounds.short_low < bounds.short_mid && bounds.short_mid < bounds.short_high);
+        assert!(bounds.tall_low < bounds.tall_mid && bounds.tall_mid < bounds.tall_high);
+        assert!(bounds.short_high - bounds.short_low < 1.0);
+    }
+
+    #[test]
+    fn test_et_sensitivity_ranks_inputs() {
+        // Given
+        let output = greeley_output(Some(1.27), Some(22.4), Some(1.94));
+
+        // When
+        let sensitivities = et_sensitivity(&output).unwrap();
+
+        let mut names: Vec<&str> = sensitivities.iter().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["ea", "rs", "tmax", "tmin", "ws"]);
+        assert!(sensitivities.iter().map(|(_, s)| s.abs()).sum::<f64>() > 0.0);
+        assert!(sensitivities
+            .windows(2)
+            .all(|pair| pair[0].1.abs() >= pair[1].1.abs()));
+        // more vapor in the air lowers ET
+        let ea = sensitivities
+            .iter()
+            .find(|(name, _)| *name == "ea")
+            .unwrap();
+        assert!(ea.1 < 0.0);
+    }
+
+    #[test]
+    fn test_dominant_input_hot_windy_dry_day() {
+        // Given
+        let day = values(38.0, 20.0, 0.8, 28.0, 6.0);
+
+        // When
+        let driver = dominant_input(&day, &RefEtConfig::default());
+
+        assert!(driver == EtDriver::Wind || driver == EtDriver::Humidity);
+    }
+
+    #[test]
+    fn test_dominant_input_calm_sunny_day() {
+        // Given, the Greeley reference day with wind adjusted to 2 m
+        let day = values(32.4, 10.9, 1.27, 22.4, 1.786);
+
+        // When
+        let driver = dominant_input(&day, &RefEtConfig::default());
+
+        assert_eq!(driver, EtDriver::Radiation);
+    }
+
+    #[test]
+    fn test_field_metadata_covers_every_field() {
+        // Given, destructuring without `..` fails to compile when a field is added
+        let result = RefEtResult {
+            short: 5.7,
+            tall: 7.3,
+            dominant_input: EtDriver::Radiation,
+            rs_decision: RsDecision::Measured,
+        };
+        let RefEtResult {
+            short: _,
+            tall: _,
+            dominant_input: _,
+            rs_decision: _,
+        } = result;
+        let fields = ["short", "tall", "dominant_input", "rs_decision"];
+
+        // When
+        let metadata = RefEtResult::field_metadata();
+
+        let names: Vec<&str> = metadata.iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(names, fields);
+        assert!(metadata
+            .iter()
+            .all(|(_, _, description)| !description.is_empty()));
+    }
+
+    #[test]
+    fn test_robust_fully_instrumented() {
+        // Given
+        let output = greeley_output(Some(1.27), Some(22.4), Some(1.94));
+
+        // When
+        let (short, tall, level) = calculate_ref_et_robust(&output).unwrap();
+
+        assert_eq!(level, DegradationLevel::Full);
+        assert!((short - 5.69).abs() < 0.01);
+        assert!((tall - 7.32).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_robust_rs_missing() {
+        // Given
+        let output = greeley_output(Some(1.27), None, Some(1.94));
+
+        // When
+        let (short, tall, level) = calculate_ref_et_robust(&output).unwrap();
+
+        assert_eq!(level, DegradationLevel::Estimated(vec![EstimatedInput::Rs]));
+        assert!(short.is_finite() && short > 0.0);
+        assert!(tall > short);
+    }
+
+    #[test]
+    fn test_robust_temperature_only() {
+        // Given
+        let output = greeley_output(None, None, None);
+
+        // When
+        let (short, tall, level) = calculate_ref_et_robust(&output).unwrap();
+
+        assert_eq!(level, DegradationLevel::Minimal);
+        assert!(short.is_finite() && short > 0.0);
+        assert!(tall.is_finite() && tall > short);
+    }
+
+    #[test]
+    fn test_robust_unusable_record() {
+        // Given, a negative Rs from a pyranometer offset error
+        let output = greeley_output(Some(1.27), Some(-0.5), Some(1.94));
+
+        // When
+        let result = calculate_ref_et_robust(&output);
+
+        assert!(matches!(result, Err(RefEtError::OutOfRange { .. })));
+    }
+
+    #[test]
+    fn test_result_reports_rs_decision() {
+        let measured = calculate_ref_et_result(&greeley_output(Some(1.27), Some(22.4), Some(1.94)));
+        let estimated = calculate_ref_et_result(&greeley_output(Some(1.27), None, Some(1.94)));
+
+        assert_eq!(measured.unwrap().rs_decision, RsDecision::Measured);
+        assert_eq!(estimated.unwrap().rs_decision, RsDecision::HargreavesUsed);
+    }
+}
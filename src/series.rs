@@ -0,0 +1,571 @@
+use crate::{calculate_ref_et, RefEtError};
+use chrono::{Datelike, NaiveDate};
+use climate::output::Output;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+
+/// Calculates the short and tall reference et for every row of a time series.
+///
+/// Each row is calculated on its own, so a bad day is reported in its slot and doesn't stop the
+/// rest of the series.
+///
+/// # Arguments
+///
+/// * `rows` - The output values for each time step.
+///
+/// # Returns
+///
+/// * One result per row, in the same order as `rows`.
+pub fn calculate_ref_et_series(rows: &[Output]) -> Vec<Result<(f64, f64), RefEtError>> {
+    rows.iter()
+        .map(|row| calculate_ref_et(row).map(Into::into))
+        .collect()
+}
+
+/// Calculates the short and tall reference et for every row of a time series across threads.
+///
+/// Rows are independent, so this returns exactly what `calculate_ref_et_series` returns, in the
+/// same order as `rows`.
+#[cfg(feature = "rayon")]
+pub fn calculate_ref_et_series_par(rows: &[Output]) -> Vec<Result<(f64, f64), RefEtError>> {
+    rows.par_iter()
+        .map(|row| calculate_ref_et(row).map(Into::into))
+        .collect()
+}
+
+/// Calculates the mean short and tall reference et over the rows that can be calculated.
+///
+/// # Arguments
+///
+/// * `rows` - The output values for each time step.
+///
+/// # Returns
+///
+/// * The mean short and tall reference ET, or `None` when no row could be calculated.
+pub fn calculate_ref_et_series_mean(rows: &[Output]) -> Option<(f64, f64)> {
+    let (count, short, tall) = calculate_ref_et_series(rows)
+        .into_iter()
+        .flatten()
+        .fold((0, 0.0, 0.0), |(count, short, tall), (s, t)| {
+            (count + 1, short + s, tall + t)
+        });
+
+    if count == 0 {
+        None
+    } else {
+        Some((short / count as f64, tall / count as f64))
+    }
+}
+
+/// Sums a daily ET series into monthly totals for water-budget reporting.
+///
+/// Rows don't need to be sorted, and NaN values are skipped. Partial months are summed as-is, so
+/// a month with missing days reports a smaller total rather than an error.
+///
+/// # Arguments
+///
+/// * `rows` - The date and ET value of each day, typically in mm/day.
+///
+/// # Returns
+///
+/// * One (year, month, sum) per month that has at least one row, in date order.
+pub fn aggregate_monthly(rows: &[(NaiveDate, f64)]) -> Vec<(u32, u32, f64)> {
+    let mut totals = BTreeMap::new();
+    for (date, value) in rows.iter().filter(|(_, value)| !value.is_nan()) {
+        *totals
+            .entry((date.year() as u32, date.month()))
+            .or_insert(0.0) += value;
+    }
+
+    totals
+        .into_iter()
+        .map(|((year, month), sum)| (year, month, sum))
+        .collect()
+}
+
+/// Sums a daily ET series into annual totals.
+///
+/// Rows don't need to be sorted, and NaN values are skipped. Partial years are summed as-is.
+///
+/// # Arguments
+///
+/// * `rows` - The date and ET value of each day, typically in mm/day.
+///
+/// # Returns
+///
+/// * One (year, sum) per year that has at least one row, in date order.
+pub fn aggregate_annual(rows: &[(NaiveDate, f64)]) -> Vec<(u32, f64)> {
+    let mut totals = BTreeMap::new();
+    for (date, value) in rows.iter().filter(|(_, value)| !value.is_nan()) {
+        *totals.entry(date.year() as u32).or_insert(0.0) += value;
+    }
+
+    totals.into_iter().collect()
+}
+
+/// Fills interior gaps of a daily series by linear interpolation between the nearest values on
+/// either side.
+///
+/// Leading and trailing `None` values have only one neighbor and are left untouched, use
+/// `fill_gaps_hold_forward` when trailing days should repeat the last value instead.
+///
+/// # Arguments
+///
+/// * `series` - The daily values in date order, with `None` for missing days.
+pub fn fill_gaps_linear(series: &mut [Option<f64>]) {
+    let mut previous: Option<(usize, f64)> = None;
+    for i in 0..series.len() {
+        let Some(value) = series[i] else {
+            continue;
+        };
+
+        if let Some((start, start_value)) = previous {
+            let span = (i - start) as f64;
+            for (offset, gap) in series[start + 1..i].iter_mut().enumerate() {
+                let fraction = (offset + 1) as f64 / span;
+                *gap = Some(start_value + (value - start_value) * fraction);
+            }
+        }
+        previous = Some((i, value));
+    }
+}
+
+/// Fills gaps of a daily series by repeating the last value before each gap.
+///
+/// Interior and trailing gaps are filled, leading `None` values have nothing to repeat and are
+/// left untouched.
+///
+/// # Arguments
+///
+/// * `series` - The daily values in date order, with `None` for missing days.
+pub fn fill_gaps_hold_forward(series: &mut [Option<f64>]) {
+    let mut last = None;
+    for value in series.iter_mut() {
+        match value {
+            Some(current) => last = Some(*current),
+            None => *value = last,
+        }
+    }
+}
+
+/// Sums a daily series over a trailing window, such as 7 or 30 days of ET for reporting.
+///
+/// The first `window - 1` days don't have a full window behind them and are NaN, so every
+/// returned total covers the same number of days. A NaN day makes every total that includes it
+/// NaN.
+///
+/// # Arguments
+///
+/// * `series` - The daily values in date order.
+/// * `window` - Number of days in each total, including the day itself.
+///
+/// # Returns
+///
+/// * The trailing total for each day, the same length as `series`.
+///
+/// # Panics
+///
+/// * Panics when `window` is 0.
+pub fn rolling_sum(series: &[f64], window: usize) -> Vec<f64> {
+    assert!(window > 0, "window must be at least one day");
+
+    let head = series.len().min(window - 1);
+    let mut totals = vec![f64::NAN; head];
+    totals.extend(series.windows(window).map(|days| days.iter().sum::<f64>()));
+    totals
+}
+
+/// Smooths a daily solar radiation series through its clear-sky index Rs/Rso, so noise in
+/// satellite Rs doesn't carry into fcd and Rnl.
+///
+/// The index is averaged over a centered window of `window` days and multiplied back by each day's
+/// Rso. Near the ends of the series the window shrinks to the days that exist, so the first day is
+/// averaged with only the days after it. Days with a NaN Rs or an Rso that isn't positive are left
+/// out of the averages, and their Rs is returned unchanged.
+///
+/// # Arguments
+///
+/// * `rs` - Daily solar radiation in MJ/m2/day, in date order.
+/// * `rso` - Daily clear-sky radiation in MJ/m2/day for the same days.
+/// * `window` - Width of the moving average in days, rounded up to an odd number. A window of 0
+///   or 1 returns the series unchanged.
+///
+/// # Returns
+///
+/// * The smoothed Rs for each day.
+///
+/// # Panics
+///
+/// * Panics when `rs` and `rso` are different lengths.
+pub fn smooth_clear_sky_index(rs: &[f64], rso: &[f64], window: usize) -> Vec<f64> {
+    assert_eq!(rs.len(), rso.len(), "rs and rso must be the same length");
+
+    let index: Vec<Option<f64>> = rs
+        .iter()
+        .zip(rso)
+        .map(|(&rs, &rso)| (rso > 0.0 && !rs.is_nan()).then(|| rs / rso))
+        .collect();
+    let half = window / 2;
+
+    (0..rs.len())
+        .map(|day| {
+            if index[day].is_none() {
+                return rs[day];
+            }
+
+            let neighbors = &index[day.saturating_sub(half)..(day + half + 1).min(rs.len())];
+            let (sum, count) = neighbors
+                .iter()
+                .flatten()
+                .fold((0.0, 0), |(sum, count), ratio| (sum + ratio, count + 1));
+            sum / count as f64 * rso[day]
+        })
+        .collect()
+}
+
+/// Statistics of one day of the year across every year of a climatology.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DayStats {
+    pub mean: f64,    // mean across years, NaN when no year has the day
+    pub std: f64,     // sample standard deviation across years, 0 for a single year
+    pub p10: f64,     // 10th percentile across years
+    pub p50: f64,     // median across years
+    pub p90: f64,     // 90th percentile across years
+    values: Vec<f64>, // the day's value in each year, sorted ascending
+}
+
+impl DayStats {
+    fn from_values(mut values: Vec<f64>) -> DayStats {
+        if values.is_empty() {
+            return DayStats {
+                mean: f64::NAN,
+                std: f64::NAN,
+                p10: f64::NAN,
+                p50: f64::NAN,
+                p90: f64::NAN,
+                values,
+            };
+        }
+
+        values.sort_by(f64::total_cmp);
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std = if values.len() > 1 {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        DayStats {
+            mean,
+            std,
+            p10: percentile(&values, 0.1),
+            p50: percentile(&values, 0.5),
+            p90: percentile(&values, 0.9),
+            values,
+        }
+    }
+
+    /// Number of years with a value for the day.
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Places a new observation in the day's historical distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The observation, in the same units as the climatology.
+    ///
+    /// # Returns
+    ///
+    /// * The percent of years below the value, counting ties as half, from 0 to 100, or NaN when
+    ///   no year has the day.
+    pub fn percentile_of(&self, value: f64) -> f64 {
+        if self.values.is_empty() {
+            return f64::NAN;
+        }
+
+        let below = self.values.iter().filter(|&&v| v < value).count() as f64;
+        let ties = self.values.iter().filter(|&&v| v == value).count() as f64;
+        100.0 * (below + 0.5 * ties) / self.values.len() as f64
+    }
+}
+
+/// Builds the day-of-year climatology of a multi-year daily ET series, for comparing a day's ET
+/// with its historical distribution in drought monitoring.
+///
+/// Days are indexed on a leap-year calendar, so index 59 is Feb 29 and index 60 is always Mar 1.
+/// In common years every day from Mar 1 on is shifted up by one, which keeps each calendar date in
+/// the same slot and leaves index 59 to the leap years alone. NaN values are skipped.
+///
+/// # Arguments
+///
+/// * `series` - The date and ET value of each day, in any order.
+///
+/// # Returns
+///
+/// * The statistics of each day of the year, from Jan 1 at index 0 to Dec 31 at index 365.
+pub fn et_climatology(series: &[(NaiveDate, f64)]) -> [DayStats; 366] {
+    let mut days: [Vec<f64>; 366] = std::array::from_fn(|_| Vec::new());
+    for (date, value) in series.iter().filter(|(_, value)| !value.is_nan()) {
+        days[climatology_index(date)].push(*value);
+    }
+
+    days.map(DayStats::from_values)
+}
+
+/// Index of a date on the leap-year calendar used by `et_climatology`.
+fn climatology_index(date: &NaiveDate) -> usize {
+    let index = date.ordinal0() as usize;
+    let leap_year = NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some();
+    if !leap_year && index >= 59 {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Percentile of sorted values by linear interpolation between the closest ranks.
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = fraction * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn greeley_row(day: u32, ws: Option<f64>) -> Output {
+        Output::new_with_values(
+            32.4,
+            10.9,
+            None,
+            None,
+            None,
+            Some(1.27),
+            Some(22.4),
+            ws,
+            Some(3.0),
+            1462.4,
+            40.41_f64.to_radians(),
+            NaiveDate::from_ymd_opt(2000, 7, day).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_series_skips_bad_row() {
+        // Given
+        let rows = vec![
+            greeley_row(1, Some(1.94)),
+            greeley_row(2, None),
+            greeley_row(3, Some(1.94)),
+        ];
+
+        // When
+        let results = calculate_ref_et_series(&rows);
+
+        assert_eq!(results.len(), 3);
+        assert!((results[0].as_ref().unwrap().0 - 5.69).abs() < 0.01);
+        assert_eq!(results[1], Err(RefEtError::MissingField("ws")));
+        assert!(results[2].is_ok());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_series_par_matches_serial() {
+        // Given, a synthetic year of rows with every seventh day missing wind
+        let rows: Vec<Output> = (0..1000)
+            .map(|i| {
+                let date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap() + chrono::Days::new(i);
+                let tmax = 15.0 + (i % 20) as f64;
+                Output::new_with_values(
+                    tmax,
+                    tmax - 12.0,
+                    None,
+                    None,
+                    None,
+                    Some(1.0),
+                    Some(10.0 + (i % 15) as f64),
+                    (i % 7 != 0).then_some(2.0 + (i % 5) as f64 * 0.5),
+                    Some(2.0),
+                    1462.4,
+                    40.41_f64.to_radians(),
+                    date,
+                )
+            })
+            .collect();
+
+        // When
+        let parallel = calculate_ref_et_series_par(&rows);
+
+        assert_eq!(parallel, calculate_ref_et_series(&rows));
+    }
+
+    #[test]
+    fn test_series_mean_uses_ok_rows() {
+        let rows = vec![greeley_row(1, Some(1.94)), greeley_row(2, None)];
+
+        let (short, tall) = calculate_ref_et_series_mean(&rows).unwrap();
+
+        assert!((short - 5.69).abs() < 0.01);
+        assert!((tall - 7.32).abs() < 0.01);
+        assert_eq!(calculate_ref_et_series_mean(&rows[1..]), None);
+    }
+
+    #[test]
+    fn test_rolling_sum_ramp() {
+        // Given, ET rising by 1 mm each day
+        let series: Vec<f64> = (1..=10).map(f64::from).collect();
+
+        // When
+        let weekly = rolling_sum(&series, 7);
+        let daily = rolling_sum(&series, 1);
+
+        assert_eq!(weekly.len(), 10);
+        assert!(weekly[..6].iter().all(|total| total.is_nan()));
+        assert_eq!(weekly[6..], [28.0, 35.0, 42.0, 49.0]);
+        assert_eq!(daily, series);
+        assert!(rolling_sum(&series, 30).iter().all(|total| total.is_nan()));
+    }
+
+    #[test]
+    fn test_smooth_clear_sky_index() {
+        // Given, a clear-sky index of 0.75 with alternating noise of ±0.1
+        let rso: Vec<f64> = (0..60)
+            .map(|day| 30.0 + 5.0 * (day as f64 / 20.0).sin())
+            .collect();
+        let rs: Vec<f64> = rso
+            .iter()
+            .enumerate()
+            .map(|(day, rso)| rso * if day % 2 == 0 { 0.85 } else { 0.65 })
+            .collect();
+
+        // When
+        let smoothed = smooth_clear_sky_index(&rs, &rso, 5);
+
+        assert_eq!(smoothed.len(), rs.len());
+        // an odd window over alternating noise leaves at most one noisy day in the average
+        assert!(smoothed[2..58]
+            .iter()
+            .zip(&rso[2..58])
+            .all(|(rs, rso)| (rs / rso - 0.75).abs() <= 0.2 / 5.0 + 1e-9));
+        // the first day averages days 0 to 2 only
+        assert!((smoothed[0] / rso[0] - (0.85 + 0.65 + 0.85) / 3.0).abs() < 1e-9);
+        assert!(smooth_clear_sky_index(&rs, &rso, 1)
+            .iter()
+            .zip(&rs)
+            .all(|(smoothed, rs)| (smoothed - rs).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_monthly_sums_match_annual() {
+        // Given, a synthetic year with a NaN day
+        let start = NaiveDate::from_ymd_opt(2001, 1, 1).unwrap();
+        let mut rows: Vec<(NaiveDate, f64)> = (0..365)
+            .map(|day| {
+                let date = start + chrono::Duration::days(day);
+                (date, 2.0 + (day as f64 / 58.0).sin())
+            })
+            .collect();
+        rows[100].1 = f64::NAN;
+
+        // When
+        let monthly = aggregate_monthly(&rows);
+        let annual = aggregate_annual(&rows);
+
+        assert_eq!(monthly.len(), 12);
+        assert_eq!(annual.len(), 1);
+        assert_eq!((monthly[0].0, monthly[0].1), (2001, 1));
+        let monthly_total: f64 = monthly.iter().map(|(_, _, sum)| sum).sum();
+        assert!((monthly_total - annual[0].1).abs() < 1e-9);
+        assert!(annual[0].1.is_finite());
+    }
+
+    #[test]
+    fn test_aggregate_partial_month() {
+        let rows = vec![
+            (NaiveDate::from_ymd_opt(2001, 3, 2).unwrap(), 4.0),
+            (NaiveDate::from_ymd_opt(2000, 12, 31).unwrap(), 1.5),
+            (NaiveDate::from_ymd_opt(2001, 3, 1).unwrap(), 3.0),
+        ];
+
+        assert_eq!(
+            aggregate_monthly(&rows),
+            vec![(2000, 12, 1.5), (2001, 3, 7.0)]
+        );
+        assert_eq!(aggregate_annual(&rows), vec![(2000, 1.5), (2001, 7.0)]);
+    }
+
+    #[test]
+    fn test_fill_gaps_single_interior_gap() {
+        let mut series = vec![Some(4.0), None, Some(6.0)];
+
+        fill_gaps_linear(&mut series);
+
+        assert_eq!(series, vec![Some(4.0), Some(5.0), Some(6.0)]);
+    }
+
+    #[test]
+    fn test_fill_gaps_two_day_gap() {
+        let mut series = vec![Some(3.0), None, None, Some(6.0)];
+
+        fill_gaps_linear(&mut series);
+
+        assert!((series[1].unwrap() - 4.0).abs() < 1e-9);
+        assert!((series[2].unwrap() - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fill_gaps_leading_and_trailing_untouched() {
+        let mut series = vec![None, Some(3.0), None, Some(5.0), None];
+
+        fill_gaps_linear(&mut series);
+
+        assert_eq!(series, vec![None, Some(3.0), Some(4.0), Some(5.0), None]);
+    }
+
+    #[test]
+    fn test_fill_gaps_hold_forward() {
+        let mut series = vec![None, Some(3.0), None, Some(5.0), None];
+
+        fill_gaps_hold_forward(&mut series);
+
+        assert_eq!(
+            series,
+            vec![None, Some(3.0), Some(3.0), Some(5.0), Some(5.0)]
+        );
+    }
+
+    #[test]
+    fn test_et_climatology_two_years() {
+        // Given, a common year of 5 mm/day followed by a leap year of 7 mm/day
+        let start = NaiveDate::from_ymd_opt(2019, 1, 1).unwrap();
+        let series: Vec<(NaiveDate, f64)> = start
+            .iter_days()
+            .take_while(|date| date.year() < 2021)
+            .map(|date| (date, if date.year() == 2019 { 5.0 } else { 7.0 }))
+            .collect();
+
+        // When
+        let climatology = et_climatology(&series);
+
+        let jan_1 = &climatology[0];
+        assert_eq!(jan_1.count(), 2);
+        assert!((jan_1.mean - 6.0).abs() < 1e-9);
+        assert!((jan_1.std - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((jan_1.p10 - 5.2).abs() < 1e-9);
+        assert!((jan_1.p50 - 6.0).abs() < 1e-9);
+        assert!((jan_1.p90 - 6.8).abs() < 1e-9);
+        assert!((jan_1.percentile_of(6.0) - 50.0).abs() < 1e-9);
+        assert!((jan_1.percentile_of(8.0) - 100.0).abs() < 1e-9);
+
+        // Feb 29 only has the leap year, and Mar 1 lines up across both years
+        assert_eq!(climatology[59].count(), 1);
+        assert!((climatology[59].mean - 7.0).abs() < 1e-9);
+        assert_eq!(climatology[60].count(), 2);
+        assert_eq!(climatology[365].count(), 2);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::conversions::day_of_year_from_str;
+use crate::et::{check_rs, ref_et_from_values, DailyValues};
+use crate::input::check_elevation;
+use crate::{EaInput, RefEtConfig, RefEtError};
+use std::f64::consts::FRAC_PI_2;
+
+/// The values of a weather station that don't change from day to day, so a service can describe
+/// the station once and then supply only the daily weather.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "StationRaw"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Station {
+    latitude_rad: f64,  // station latitude in radians
+    elevation_m: f64,   // station elevation above sea level in meters
+    wind_height_m: f64, // height of the anemometer above the ground in meters
+}
+
+/// A station as read from a file, checked by `Station::new` before it becomes a `Station`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct StationRaw {
+    latitude_rad: f64,
+    elevation_m: f64,
+    wind_height_m: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StationRaw> for Station {
+    type Error = RefEtError;
+
+    fn try_from(raw: StationRaw) -> Result<Station, RefEtError> {
+        Station::new(raw.latitude_rad, raw.elevation_m, raw.wind_height_m)
+    }
+}
+
+impl Station {
+    /// Creates a station.
+    ///
+    /// # Returns
+    ///
+    /// * The station, or `OutOfRange` when the latitude is beyond ±π/2, the elevation is outside
+    ///   -500 to 9000 m, or the wind height isn't above the ground.
+    pub fn new(
+        latitude_rad: f64,
+        elevation_m: f64,
+        wind_height_m: f64,
+    ) -> Result<Station, RefEtError> {
+        if !(-FRAC_PI_2..=FRAC_PI_2).contains(&latitude_rad) {
+            return Err(RefEtError::OutOfRange {
+                field: "latitude",
+                value: latitude_rad,
+            });
+        }
+        check_elevation(elevation_m)?;
+        if wind_height_m.is_nan() || wind_height_m <= 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "wind_height",
+                value: wind_height_m,
+            });
+        }
+
+        Ok(Station {
+            latitude_rad,
+            elevation_m,
+            wind_height_m,
+        })
+    }
+
+    pub fn latitude_rad(&self) -> f64 {
+        self.latitude_rad
+    }
+
+    pub fn elevation_m(&self) -> f64 {
+        self.elevation_m
+    }
+
+    pub fn wind_height_m(&self) -> f64 {
+        self.wind_height_m
+    }
+
+    /// Calculates the short and tall reference et for one day at the station.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The day in the format "YYYY-MM-DD".
+    /// * `tmax` - Daily maximum air temperature in Celsius.
+    /// * `tmin` - Daily minimum air temperature in Celsius.
+    /// * `ea` - The humidity input for the day.
+    /// * `rs` - Measured solar radiation in MJ/m2/day, estimated with Hargreaves-Samani when None.
+    /// * `ws` - Wind speed in m/s measured at the station's wind height.
+    ///
+    /// # Returns
+    ///
+    /// * a tuple containing the short and tall reference evapotranspiration, or an error when the
+    ///   date is malformed, tmin exceeds tmax, rs or ws is negative or NaN, or ea can't be
+    ///   calculated.
+    pub fn ref_et(
+        &self,
+        date: &str,
+        tmax: f64,
+        tmin: f64,
+        ea: EaInput,
+        rs: Option<f64>,
+        ws: f64,
+    ) -> Result<(f64, f64), RefEtError> {
+        if tmin > tmax {
+            return Err(RefEtError::DomainError("tmin must not exceed tmax"));
+        }
+        check_rs(rs)?;
+        if ws.is_nan() || ws < 0.0 {
+            return Err(RefEtError::OutOfRange {
+                field: "ws",
+                value: ws,
+            });
+        }
+
+        let values = DailyValues {
+            tmax,
+            tmin,
+            ea: ea.ea()?,
+            rs,
+            rn: None,
+            sunshine: None,
+            cloud: None,
+            ws,
+            wz: self.wind_height_m,
+            z: self.elevation_m,
+            pressure: None,
+            latitude: self.latitude_rad,
+            doy: day_of_year_from_str(date)?,
+            g: 0.0,
+        };
+
+        Ok(ref_et_from_values(&values, &RefEtConfig::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn greeley() -> Station {
+        Station::new(40.41_f64.to_radians(), 1462.4, 3.0).unwrap()
+    }
+
+    #[test]
+    fn test_station_several_days() {
+        // Given
+        let station = greeley();
+        let days = [
+            ("2000-07-01", 32.4, 10.9, 1.27, 22.4, 1.94),
+            ("2000-07-02", 30.1, 12.0, 1.35, 20.8, 2.6),
+            ("2000-07-03", 27.5, 14.2, 1.52, 15.3, 3.1),
+        ];
+
+        // When
+        let results: Vec<(f64, f64)> = days
+            .iter()
+            .map(|&(date, tmax, tmin, ea, rs, ws)| {
+                let ea = EaInput::new_direct(ea, "kPa").unwrap();
+                station.ref_et(date, tmax, tmin, ea, Some(rs), ws).unwrap()
+            })
+            .collect();
+
+        assert!((results[0].0 - 5.69).abs() < 0.01);
+        assert!((results[0].1 - 7.32).abs() < 0.01);
+        assert!(results
+            .iter()
+            .all(|&(short, tall)| short > 0.0 && tall > short));
+    }
+
+    #[test]
+    fn test_station_rejects_bad_values() {
+        assert!(Station::new(40.41, 1462.4, 3.0).is_err());
+        assert!(Station::new(0.7, 1462.4, 0.0).is_err());
+        assert!(Station::new(0.7, 4797.9 * 3.0, 3.0).is_err());
+
+        let ea = || EaInput::new_direct(1.27, "kPa").unwrap();
+        assert!(greeley()
+            .ref_et("2000-13-01", 32.4, 10.9, ea(), None, 1.94)
+            .is_err());
+        assert!(greeley()
+            .ref_et("2000-07-01", 32.4, 10.9, ea(), None, -1.0)
+            .is_err());
+        assert!(greeley()
+            .ref_et("2000-07-01", 32.4, 10.9, ea(), None, f64::NAN)
+            .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_station_json_round_trip() {
+        // Given
+        let json = r#"{"latitude_rad":0.7053,"elevation_m":1462.4,"wind_height_m":3.0}"#;
+
+        // When
+        let station: Station = serde_json::from_str(json).unwrap();
+
+        assert_eq!(station.elevation_m(), 1462.4);
+        assert_eq!(serde_json::to_string(&station).unwrap(), json);
+        let invalid = r#"{"latitude_rad":40.41,"elevation_m":1462.4,"wind_height_m":3.0}"#;
+        assert!(serde_json::from_str::<Station>(invalid).is_err());
+    }
+}